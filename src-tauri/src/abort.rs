@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+use crate::command_output;
+
+/// How long the server gets to wind down after an interrupt before leftover
+/// tool processes are killed.
+const GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// How often tool processes are checked during the grace period.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long terminated tool processes get to exit before being force-killed.
+#[cfg(unix)]
+const KILL_DELAY: Duration = Duration::from_secs(1);
+
+/// What `abort_all` did, as sent to the frontend.
+#[derive(Clone, Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbortSummary {
+    /// Conversations the server was asked to interrupt.
+    pub interrupted: Vec<String>,
+    /// PIDs of tool processes that were killed.
+    pub killed: Vec<u32>,
+}
+
+/// A running OS process.
+struct Process {
+    pid: u32,
+    parent: u32,
+    #[cfg(unix)]
+    group: u32,
+    name: String,
+}
+
+#[cfg(unix)]
+fn processes() -> Vec<Process> {
+    let Some(stdout) = command_output("ps", &["-A", "-o", "pid=,ppid=,pgid=,comm="]) else {
+        return Vec::new();
+    };
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Process {
+                pid: fields.next()?.parse().ok()?,
+                parent: fields.next()?.parse().ok()?,
+                group: fields.next()?.parse().ok()?,
+                name: fields.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn processes() -> Vec<Process> {
+    let script = "Get-CimInstance Win32_Process | ForEach-Object { \"$($_.ProcessId) $($_.ParentProcessId) $($_.Name)\" }";
+    let Some(stdout) = command_output("powershell", &["-NoProfile", "-Command", script]) else {
+        return Vec::new();
+    };
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Process {
+                pid: fields.next()?.parse().ok()?,
+                parent: fields.next()?.parse().ok()?,
+                name: fields.collect::<Vec<_>>().join(" "),
+            })
+        })
+        .collect()
+}
+
+/// Pick the tool processes out of a process list.
+///
+/// The server keeps long-lived helpers as direct children, such as a
+/// persistent shell or interpreter session, and runs tool commands inside
+/// them. Only what the helpers started is returned, so an abort stops the
+/// commands but keeps the sessions the server expects to reuse. The sidecar
+/// may re-exec itself (e.g. a PyInstaller bootloader), so processes named
+/// gptme-server count as the server itself.
+fn select_tools(processes: Vec<Process>, server_pid: u32) -> Vec<Process> {
+    let mut children: HashMap<u32, Vec<Process>> = HashMap::new();
+    for process in processes {
+        children.entry(process.parent).or_default().push(process);
+    }
+
+    let mut helpers = Vec::new();
+    let mut servers = vec![server_pid];
+    while let Some(pid) = servers.pop() {
+        for process in children.remove(&pid).unwrap_or_default() {
+            if process.name.contains("gptme-server") {
+                servers.push(process.pid);
+            } else {
+                helpers.push(process.pid);
+            }
+        }
+    }
+
+    let mut found = Vec::new();
+    let mut queue = helpers;
+    while let Some(pid) = queue.pop() {
+        for process in children.remove(&pid).unwrap_or_default() {
+            queue.push(process.pid);
+            found.push(process);
+        }
+    }
+    found
+}
+
+/// Tool processes currently running under gptme-server.
+fn tool_processes(server_pid: u32) -> Vec<Process> {
+    select_tools(processes(), server_pid)
+}
+
+/// Kill tool processes, by process group where they have their own.
+#[cfg(unix)]
+fn kill(tools: &[Process]) {
+    // A negative PID signals the whole group. Only groups led by a tool are
+    // signalled that way; tools sharing a group with the server or one of
+    // its helpers are signalled one by one so those survive
+    let mut targets: Vec<String> = tools
+        .iter()
+        .map(|p| {
+            if tools.iter().any(|leader| leader.pid == p.group) {
+                format!("-{}", p.group)
+            } else {
+                p.pid.to_string()
+            }
+        })
+        .collect();
+    targets.sort();
+    targets.dedup();
+
+    for signal in ["-TERM", "-KILL"] {
+        let mut args = vec![signal, "--"];
+        args.extend(targets.iter().map(String::as_str));
+        // Fails for targets that already exited, which is fine
+        let _ = command_output("kill", &args);
+        if signal == "-TERM" {
+            std::thread::sleep(KILL_DELAY);
+        }
+    }
+}
+
+/// Kill tool processes along with their child trees.
+#[cfg(windows)]
+fn kill(tools: &[Process]) {
+    for process in tools {
+        let pid = process.pid.to_string();
+        let _ = command_output("taskkill", &["/PID", &pid, "/T", "/F"]);
+    }
+}
+
+/// Conversations open in any tab or window.
+fn open_conversations(app: &tauri::AppHandle) -> Vec<String> {
+    let tabs = crate::tabs::snapshot(app)
+        .map(|snapshot| snapshot.tabs)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|tab| tab.conversation_id);
+    let windows = crate::windows::list(app)
+        .into_iter()
+        .filter_map(|window| window.conversation_id);
+
+    let mut conversations: Vec<String> = tabs.chain(windows).collect();
+    conversations.sort();
+    conversations.dedup();
+    conversations
+}
+
+/// Subscribe to a conversation's events to get a server session for it.
+///
+/// The session only lives as long as the returned stream is kept open.
+fn connect(
+    client: &reqwest::blocking::Client,
+    conversation_id: &str,
+) -> Result<(String, BufReader<reqwest::blocking::Response>), String> {
+    let response = client
+        .get(crate::events::server_url(&format!(
+            "/api/v2/conversations/{}/events",
+            conversation_id
+        )))
+        .send()
+        .map_err(|e| format!("Could not reach gptme-server: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("gptme-server returned {}", response.status()));
+    }
+
+    let mut reader = BufReader::new(response);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Failed to read event stream: {}", e))?;
+        if read == 0 {
+            return Err("Event stream closed before connecting".to_string());
+        }
+        let Some(data) = line.trim_end().strip_prefix("data:") else {
+            continue;
+        };
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(data.trim()) else {
+            continue;
+        };
+        if event.get("type").and_then(|v| v.as_str()) == Some("connected") {
+            let session_id = event
+                .get("session_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Connected event has no session ID")?;
+            return Ok((session_id.to_string(), reader));
+        }
+    }
+}
+
+/// Ask the server to stop generating in a conversation.
+fn interrupt(
+    client: &reqwest::blocking::Client,
+    conversation_id: &str,
+    session_id: &str,
+) -> Result<(), String> {
+    let response = client
+        .post(crate::events::server_url(&format!(
+            "/api/v2/conversations/{}/interrupt",
+            conversation_id
+        )))
+        .json(&serde_json::json!({ "session_id": session_id }))
+        .send()
+        .map_err(|e| format!("Could not reach gptme-server: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("gptme-server returned {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Ask the server to stop generating in every open conversation, returning
+/// the ones it accepted the interrupt for.
+fn interrupt_all(app: &tauri::AppHandle) -> Vec<String> {
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(GRACE_PERIOD)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            log::error!("Failed to create HTTP client for abort: {}", e);
+            return Vec::new();
+        }
+    };
+
+    // The event bridge already holds a session for the active conversation
+    let bridge = app.state::<crate::events::EventBridge>();
+    let bridged = bridge.conversation().zip(bridge.session_id());
+
+    let mut interrupted = Vec::new();
+    for conversation_id in open_conversations(app) {
+        let result = match &bridged {
+            Some((bridged_id, session_id)) if *bridged_id == conversation_id => {
+                interrupt(&client, &conversation_id, session_id)
+            }
+            _ => connect(&client, &conversation_id).and_then(|(session_id, _stream)| {
+                interrupt(&client, &conversation_id, &session_id)
+            }),
+        };
+        match result {
+            Ok(()) => interrupted.push(conversation_id),
+            Err(e) => log::error!("Failed to interrupt {}: {}", conversation_id, e),
+        }
+    }
+    interrupted
+}
+
+/// Interrupt the agent, then kill any tool processes that outlive the grace
+/// period. Blocks for up to a few seconds.
+pub fn abort(app: &tauri::AppHandle) -> AbortSummary {
+    log::warn!("Aborting all agent activity");
+    crate::ensure_server_started(app);
+    let mut summary = AbortSummary {
+        interrupted: interrupt_all(app),
+        ..Default::default()
+    };
+
+    let server_pid = app
+        .state::<crate::ServerProcess>()
+        .0
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|child| child.pid()));
+    if let Some(server_pid) = server_pid {
+        // Give interrupted tools the grace period to exit on their own
+        let deadline = Instant::now() + GRACE_PERIOD;
+        let mut tools = tool_processes(server_pid);
+        while !tools.is_empty() && Instant::now() < deadline {
+            std::thread::sleep(POLL_INTERVAL);
+            tools = tool_processes(server_pid);
+        }
+
+        if !tools.is_empty() {
+            log::warn!(
+                "Tools still running after interrupt, killing {} processes",
+                tools.len()
+            );
+            kill(&tools);
+            summary.killed = tools.iter().map(|p| p.pid).collect();
+        }
+    }
+
+    log::info!("Abort finished: {:?}", summary);
+    if let Err(e) = app.emit("agents-aborted", &summary) {
+        log::warn!("Failed to emit agents-aborted event: {}", e);
+    }
+    summary
+}
+
+/// Stop all agent activity: interrupt generation and, if tools keep
+/// running, kill their processes.
+#[tauri::command]
+pub async fn abort_all(app: tauri::AppHandle) -> Result<AbortSummary, String> {
+    // Talking to the server and waiting on processes blocks
+    tauri::async_runtime::spawn_blocking(move || abort(&app))
+        .await
+        .map_err(|e| format!("Abort failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, parent: u32, name: &str) -> Process {
+        Process {
+            pid,
+            parent,
+            #[cfg(unix)]
+            group: pid,
+            name: name.to_string(),
+        }
+    }
+
+    fn pids(processes: Vec<Process>) -> Vec<u32> {
+        let mut pids: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+        pids.sort();
+        pids
+    }
+
+    #[test]
+    fn select_tools_skips_idle_helpers() {
+        let processes = vec![
+            process(1, 0, "init"),
+            process(10, 1, "gptme-server"),
+            process(11, 10, "bash"),
+            process(12, 10, "python"),
+        ];
+        assert!(select_tools(processes, 10).is_empty());
+    }
+
+    #[test]
+    fn select_tools_returns_commands_under_helpers() {
+        let processes = vec![
+            process(10, 1, "gptme-server"),
+            process(11, 10, "bash"),
+            process(20, 11, "npm"),
+            process(21, 20, "node"),
+            process(30, 99, "unrelated"),
+        ];
+        assert_eq!(pids(select_tools(processes, 10)), vec![20, 21]);
+    }
+
+    #[test]
+    fn select_tools_walks_through_reexecuted_server() {
+        let processes = vec![
+            process(10, 1, "gptme-server"),
+            process(11, 10, "gptme-server"),
+            process(12, 11, "bash"),
+            process(13, 12, "sleep"),
+        ];
+        assert_eq!(pids(select_tools(processes, 10)), vec![13]);
+    }
+}
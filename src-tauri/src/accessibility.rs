@@ -0,0 +1,207 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+use crate::command_output;
+
+/// How often OS accessibility settings are re-read. None of the platforms
+/// we support offer a cheap change notification without extra native deps.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Accessibility preferences read from the operating system.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilityPreferences {
+    pub high_contrast: bool,
+    pub reduced_motion: bool,
+    /// Text scaling factor relative to the platform default (1.0 = 100%).
+    pub font_scale: f64,
+}
+
+impl Default for AccessibilityPreferences {
+    fn default() -> Self {
+        Self {
+            high_contrast: false,
+            reduced_motion: false,
+            font_scale: 1.0,
+        }
+    }
+}
+
+/// Managed state holding the most recently detected preferences.
+pub struct AccessibilityState(pub Mutex<AccessibilityPreferences>);
+
+#[cfg(target_os = "macos")]
+fn detect() -> AccessibilityPreferences {
+    let flag = |key: &str| {
+        command_output("defaults", &["read", "com.apple.universalaccess", key]).as_deref()
+            == Some("1")
+    };
+    AccessibilityPreferences {
+        high_contrast: flag("increaseContrast"),
+        reduced_motion: flag("reduceMotion"),
+        // macOS has no global text size setting that applies to webviews
+        font_scale: 1.0,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn detect() -> AccessibilityPreferences {
+    // `reg query` prints e.g. "    Flags    REG_SZ    126"; the value is the last field
+    let reg_value = |key: &str, name: &str| {
+        command_output("reg", &["query", key, "/v", name])
+            .and_then(|out| out.split_whitespace().last().map(str::to_string))
+    };
+
+    // HCF_HIGHCONTRASTON is bit 0 of the HighContrast flags
+    let high_contrast = reg_value(r"HKCU\Control Panel\Accessibility\HighContrast", "Flags")
+        .and_then(|v| v.parse::<u32>().ok())
+        .map(|flags| flags & 1 == 1)
+        .unwrap_or(false);
+
+    // "Show animations in Windows" off sets MinAnimate to 0
+    let reduced_motion = reg_value(r"HKCU\Control Panel\Desktop\WindowMetrics", "MinAnimate")
+        .map(|v| v == "0")
+        .unwrap_or(false);
+
+    // TextScaleFactor is a REG_DWORD percentage, printed as hex (e.g. 0x7d)
+    let font_scale = reg_value(r"HKCU\Software\Microsoft\Accessibility", "TextScaleFactor")
+        .and_then(|v| u32::from_str_radix(v.trim_start_matches("0x"), 16).ok())
+        .map(|percent| f64::from(percent) / 100.0)
+        .unwrap_or(1.0);
+
+    AccessibilityPreferences {
+        high_contrast,
+        reduced_motion,
+        font_scale,
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn detect() -> AccessibilityPreferences {
+    // GNOME settings are the closest thing to a desktop-wide standard on Linux
+    let gsetting = |schema: &str, key: &str| command_output("gsettings", &["get", schema, key]);
+
+    let high_contrast =
+        gsetting("org.gnome.desktop.a11y.interface", "high-contrast").as_deref() == Some("true");
+    let reduced_motion =
+        gsetting("org.gnome.desktop.interface", "enable-animations").as_deref() == Some("false");
+    let font_scale = gsetting("org.gnome.desktop.interface", "text-scaling-factor")
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(1.0);
+
+    AccessibilityPreferences {
+        high_contrast,
+        reduced_motion,
+        font_scale,
+    }
+}
+
+/// Build a script that exposes the preferences to a webview.
+///
+/// Sets `data-high-contrast` / `data-reduced-motion` attributes and a
+/// `--gptme-font-scale` CSS variable on the root element, and disables
+/// transitions and animations when reduced motion is requested.
+pub fn script(prefs: &AccessibilityPreferences) -> String {
+    format!(
+        r#"(function() {{
+  var root = document.documentElement;
+  root.dataset.highContrast = "{high_contrast}";
+  root.dataset.reducedMotion = "{reduced_motion}";
+  root.style.setProperty("--gptme-font-scale", "{font_scale}");
+  var style = document.getElementById("gptme-a11y-style");
+  if (!style) {{
+    style = document.createElement("style");
+    style.id = "gptme-a11y-style";
+    document.head.appendChild(style);
+  }}
+  style.textContent = {reduced_motion} ?
+    "*, *::before, *::after {{ animation: none !important; transition: none !important; scroll-behavior: auto !important; }}" :
+    "";
+}})();"#,
+        high_contrast = prefs.high_contrast,
+        reduced_motion = prefs.reduced_motion,
+        font_scale = prefs.font_scale,
+    )
+}
+
+/// Apply the current preferences to every open webview, including tabs.
+pub fn apply_to_all(app: &tauri::AppHandle) {
+    let prefs = current(app);
+    let js = script(&prefs);
+    for (label, webview) in app.webviews() {
+        if let Err(e) = webview.eval(&js) {
+            log::warn!(
+                "Failed to apply accessibility preferences to '{}': {}",
+                label,
+                e
+            );
+        }
+    }
+}
+
+/// Re-apply the preferences after a webview finished (re)loading its page.
+pub fn on_page_load(webview: &tauri::Webview, payload: &tauri::webview::PageLoadPayload<'_>) {
+    if payload.event() != tauri::webview::PageLoadEvent::Finished {
+        return;
+    }
+    let prefs = current(webview.app_handle());
+    if let Err(e) = webview.eval(&script(&prefs)) {
+        log::warn!("Failed to apply accessibility preferences: {}", e);
+    }
+}
+
+/// Return the most recently detected preferences.
+pub fn current(app: &tauri::AppHandle) -> AccessibilityPreferences {
+    app.try_state::<AccessibilityState>()
+        .and_then(|state| state.0.lock().ok().map(|guard| guard.clone()))
+        .unwrap_or_default()
+}
+
+/// Re-read OS preferences and notify the UI if they changed.
+fn refresh(app: &tauri::AppHandle) {
+    let detected = detect();
+    let changed = {
+        let state = app.state::<AccessibilityState>();
+        let Ok(mut guard) = state.0.lock() else {
+            log::error!("Failed to acquire lock on accessibility state");
+            return;
+        };
+        if *guard == detected {
+            false
+        } else {
+            *guard = detected.clone();
+            true
+        }
+    };
+
+    if changed {
+        log::info!("Accessibility preferences changed: {:?}", detected);
+        if let Err(e) = app.emit("accessibility-changed", &detected) {
+            log::warn!("Failed to emit accessibility-changed event: {}", e);
+        }
+        apply_to_all(app);
+    }
+}
+
+/// Detect the initial preferences and keep polling for changes.
+pub fn init(app: &tauri::AppHandle) {
+    let initial = detect();
+    log::info!("Detected accessibility preferences: {:?}", initial);
+    app.manage(AccessibilityState(Mutex::new(initial)));
+
+    let handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+        refresh(&handle);
+    });
+}
+
+/// Get the OS accessibility preferences (high contrast, reduced motion, font scale).
+///
+/// Returns the cached values, kept fresh by the poller, so the UI never
+/// waits on the settings query tools.
+#[tauri::command]
+pub fn get_accessibility_preferences(app: tauri::AppHandle) -> AccessibilityPreferences {
+    current(&app)
+}
@@ -0,0 +1,152 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::Manager;
+use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
+
+const SETTINGS_FILE: &str = "autostart.json";
+
+/// Passed to the app when the OS launches it at login, so it can tell a
+/// login launch apart from the user opening it.
+const AUTOSTART_ARG: &str = "--autostart";
+
+/// How the app behaves when launched at login. Whether it launches at all is
+/// owned by the OS (launch agent, registry key or XDG autostart entry).
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutostartSettings {
+    /// Keep the main window hidden, leaving only the tray icon.
+    pub start_hidden: bool,
+    /// When starting hidden, don't start gptme-server until the main window
+    /// is first shown.
+    pub lazy_server: bool,
+}
+
+/// Launch-at-login status as sent to the frontend.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartStatus {
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub settings: AutostartSettings,
+}
+
+/// Managed state for launch-at-login.
+pub struct AutostartState {
+    settings: Mutex<AutostartSettings>,
+    /// Set while the server start is being held back until the window shows.
+    server_deferred: AtomicBool,
+}
+
+/// The autostart plugin, configured to pass `AUTOSTART_ARG` on login launches.
+pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_autostart::init(MacosLauncher::LaunchAgent, Some(vec![AUTOSTART_ARG]))
+}
+
+/// Whether the OS will launch the app at login.
+pub fn is_enabled(app: &tauri::AppHandle) -> bool {
+    app.autolaunch().is_enabled().unwrap_or_else(|e| {
+        log::warn!("Failed to read launch-at-login state: {}", e);
+        false
+    })
+}
+
+/// Register or remove the app's login item.
+pub fn set_enabled(app: &tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| format!("Failed to update launch at login: {}", e))?;
+    log::info!(
+        "Launch at login {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    crate::tray::rebuild_menu(app);
+    Ok(())
+}
+
+/// Load settings and show the main window, unless this is a login launch
+/// that should start hidden. The window is created invisible so a hidden
+/// start never flashes it on screen.
+///
+/// Returns true if the server start should be deferred until the main
+/// window is shown.
+pub fn init(app: &tauri::AppHandle) -> bool {
+    let settings: AutostartSettings = crate::config::load(app, SETTINGS_FILE);
+    let launched_at_login = std::env::args().any(|arg| arg == AUTOSTART_ARG);
+    let defer_server = launched_at_login && settings.start_hidden && settings.lazy_server;
+
+    if launched_at_login {
+        log::info!("Launched at login with {:?}", settings);
+    }
+    if !(launched_at_login && settings.start_hidden) {
+        if let Some(window) = app.get_window("main") {
+            let _ = window.show();
+        }
+    }
+
+    app.manage(AutostartState {
+        settings: Mutex::new(settings),
+        server_deferred: AtomicBool::new(defer_server),
+    });
+    defer_server
+}
+
+/// Clear a deferred server start, returning whether one was pending.
+pub fn take_deferred_server(app: &tauri::AppHandle) -> bool {
+    app.try_state::<AutostartState>()
+        .is_some_and(|state| state.server_deferred.swap(false, Ordering::SeqCst))
+}
+
+/// Start a deferred server the first time the main window gets focus.
+pub fn on_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    if window.label() != "main" || !matches!(event, tauri::WindowEvent::Focused(true)) {
+        return;
+    }
+    let app = window.app_handle().clone();
+    tauri::async_runtime::spawn_blocking(move || crate::ensure_server_started(&app));
+}
+
+/// Get the launch-at-login state and settings.
+#[tauri::command]
+pub fn get_autostart_settings(app: tauri::AppHandle) -> AutostartStatus {
+    let settings = app
+        .state::<AutostartState>()
+        .settings
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_default();
+    AutostartStatus {
+        enabled: is_enabled(&app),
+        settings,
+    }
+}
+
+/// Enable or disable launching the app at login.
+#[tauri::command]
+pub fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    set_enabled(&app, enabled)
+}
+
+/// Update (and persist) how the app behaves when launched at login.
+///
+/// Takes effect on the next login launch.
+#[tauri::command]
+pub fn set_autostart_settings(
+    app: tauri::AppHandle,
+    settings: AutostartSettings,
+) -> Result<(), String> {
+    {
+        let state = app.state::<AutostartState>();
+        let mut guard = state
+            .settings
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        *guard = settings.clone();
+    }
+    crate::config::save(&app, SETTINGS_FILE, &settings)?;
+    log::info!("Autostart settings updated: {:?}", settings);
+    Ok(())
+}
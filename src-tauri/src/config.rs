@@ -0,0 +1,52 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::Manager;
+
+fn config_path(app: &tauri::AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join(file_name))
+        .map_err(|e| format!("Failed to resolve config dir: {}", e))
+}
+
+/// Load a JSON file from the app config dir, falling back to defaults if it
+/// is missing or unreadable.
+pub fn load<T: DeserializeOwned + Default>(app: &tauri::AppHandle, file_name: &str) -> T {
+    let path = match config_path(app, file_name) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("{}", e);
+            return T::default();
+        }
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid {}: {}", path.display(), e);
+            T::default()
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+        Err(e) => {
+            log::warn!("Failed to read {}: {}", path.display(), e);
+            T::default()
+        }
+    }
+}
+
+/// Save a value as JSON in the app config dir.
+///
+/// Writes to a temporary file first so a crash can't leave a truncated file.
+pub fn save<T: Serialize>(
+    app: &tauri::AppHandle,
+    file_name: &str,
+    value: &T,
+) -> Result<(), String> {
+    let path = config_path(app, file_name)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
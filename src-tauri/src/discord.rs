@@ -0,0 +1,224 @@
+use discord_rich_presence::{activity, DiscordIpc, DiscordIpcClient};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use tauri::Manager;
+
+const SETTINGS_FILE: &str = "discord.json";
+
+/// Discord application ID, baked in at build time. Builds without one can't
+/// publish a presence at all, regardless of settings.
+const CLIENT_ID: Option<&str> = option_env!("GPTME_DISCORD_CLIENT_ID");
+
+/// What the Discord integration is allowed to publish. Off by default.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiscordSettings {
+    /// Master switch. When off, no connection to Discord is made at all.
+    pub enabled: bool,
+    /// Include the workspace folder name (never the full path).
+    pub show_workspace: bool,
+    /// Include the current activity, e.g. "Generating…".
+    pub show_activity: bool,
+}
+
+impl Default for DiscordSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            show_workspace: false,
+            show_activity: true,
+        }
+    }
+}
+
+/// Messages to the presence worker thread.
+enum Message {
+    Update {
+        settings: DiscordSettings,
+        conversation_id: Option<String>,
+        activity: String,
+    },
+    Shutdown,
+}
+
+/// Managed state for the Discord integration.
+#[derive(Default)]
+pub struct DiscordState {
+    settings: Mutex<DiscordSettings>,
+    /// Channel to the worker thread; `None` while disabled.
+    worker: Mutex<Option<Sender<Message>>>,
+}
+
+/// Look up the workspace folder name of a conversation on the local server.
+fn workspace_name(conversation_id: &str) -> Option<String> {
+    let url = crate::events::server_url(&format!("/api/v2/conversations/{}", conversation_id));
+    let response: serde_json::Value = reqwest::blocking::get(url).ok()?.json().ok()?;
+    let workspace = response.get("workspace")?.as_str()?;
+    std::path::Path::new(workspace)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// Own the Discord IPC connection, (re)connecting lazily on each update.
+fn run_worker(client_id: &str, rx: Receiver<Message>) {
+    let mut client: Option<DiscordIpcClient> = None;
+    let mut workspaces: HashMap<String, Option<String>> = HashMap::new();
+
+    while let Ok(message) = rx.recv() {
+        let Message::Update {
+            settings,
+            conversation_id,
+            activity,
+        } = message
+        else {
+            break;
+        };
+
+        let workspace = match (&conversation_id, settings.show_workspace) {
+            (Some(id), true) => workspaces
+                .entry(id.clone())
+                .or_insert_with(|| workspace_name(id))
+                .clone(),
+            _ => None,
+        };
+        let details = match workspace {
+            Some(workspace) => format!("Working with gptme on {}", workspace),
+            None => "Working with gptme".to_string(),
+        };
+
+        if client.is_none() {
+            match DiscordIpcClient::new(client_id).and_then(|mut c| c.connect().map(|_| c)) {
+                Ok(c) => {
+                    log::info!("Connected to Discord");
+                    client = Some(c);
+                }
+                Err(e) => {
+                    log::debug!("Discord not available: {}", e);
+                    continue;
+                }
+            }
+        }
+
+        let mut presence = activity::Activity::new().details(&details);
+        if settings.show_activity {
+            presence = presence.state(&activity);
+        }
+        if let Some(c) = client.as_mut() {
+            if let Err(e) = c.set_activity(presence) {
+                log::warn!("Failed to update Discord presence: {}", e);
+                client = None;
+            }
+        }
+    }
+
+    if let Some(mut c) = client {
+        let _ = c.clear_activity();
+        let _ = c.close();
+        log::info!("Disconnected from Discord");
+    }
+}
+
+/// Send the current status to Discord, if enabled.
+pub fn update(app: &tauri::AppHandle, activity: &str) {
+    let Some(state) = app.try_state::<DiscordState>() else {
+        return;
+    };
+    let Ok(worker) = state.worker.lock() else {
+        return;
+    };
+    let Some(tx) = worker.as_ref() else {
+        return;
+    };
+    let settings = match state.settings.lock() {
+        Ok(guard) => guard.clone(),
+        Err(_) => return,
+    };
+    let _ = tx.send(Message::Update {
+        settings,
+        conversation_id: app.state::<crate::events::EventBridge>().conversation(),
+        activity: activity.to_string(),
+    });
+}
+
+/// Reject settings this build can't honour.
+fn validate(settings: &DiscordSettings) -> Result<(), String> {
+    if settings.enabled && CLIENT_ID.is_none() {
+        return Err("This build has no Discord application ID".to_string());
+    }
+    Ok(())
+}
+
+/// Start or stop the worker to match the settings.
+fn apply(app: &tauri::AppHandle, settings: &DiscordSettings) -> Result<(), String> {
+    let state = app.state::<DiscordState>();
+    {
+        let mut worker = state
+            .worker
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        if !settings.enabled {
+            if let Some(tx) = worker.take() {
+                let _ = tx.send(Message::Shutdown);
+            }
+            return Ok(());
+        }
+        if worker.is_none() {
+            let client_id =
+                CLIENT_ID.ok_or_else(|| "This build has no Discord application ID".to_string())?;
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || run_worker(client_id, rx));
+            *worker = Some(tx);
+        }
+    }
+    update(app, &crate::status::current(app));
+    Ok(())
+}
+
+/// Load settings and connect if the integration was enabled.
+pub fn init(app: &tauri::AppHandle) {
+    let mut settings: DiscordSettings = crate::config::load(app, SETTINGS_FILE);
+    if let Err(e) = validate(&settings) {
+        log::info!("Discord integration disabled: {}", e);
+        settings.enabled = false;
+    }
+    app.manage(DiscordState {
+        settings: Mutex::new(settings.clone()),
+        worker: Mutex::new(None),
+    });
+    if let Err(e) = apply(app, &settings) {
+        log::warn!("Discord integration not started: {}", e);
+    }
+}
+
+/// Get the Discord Rich Presence settings.
+#[tauri::command]
+pub fn get_discord_settings(state: tauri::State<'_, DiscordState>) -> DiscordSettings {
+    state
+        .settings
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_default()
+}
+
+/// Update (and persist) the Discord Rich Presence settings.
+///
+/// Fails without saving if the settings can't be applied. Disabling immediately clears the presence and closes the connection.
+#[tauri::command]
+pub fn set_discord_settings(
+    app: tauri::AppHandle,
+    settings: DiscordSettings,
+) -> Result<(), String> {
+    validate(&settings)?;
+    crate::config::save(&app, SETTINGS_FILE, &settings)?;
+    {
+        let state = app.state::<DiscordState>();
+        let mut guard = state
+            .settings
+            .lock()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        *guard = settings.clone();
+    }
+    log::info!("Discord settings updated: {:?}", settings);
+    apply(&app, &settings)
+}
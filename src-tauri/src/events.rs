@@ -0,0 +1,236 @@
+use std::io::{self, BufRead, BufReader};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// How often the active conversation is re-checked.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a read on the event stream may block. An idle stream wakes up
+/// this often so a reader for a conversation that is no longer active can
+/// notice and close its connection.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Delay before reconnecting after the event stream dropped or failed to open
+/// (e.g. while gptme-server is still starting up).
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// A single event from gptme-server's conversation event stream.
+///
+/// `event` is forwarded verbatim; its `type` field is one of e.g.
+/// `generation_started`, `generation_progress`, `generation_complete`,
+/// `tool_pending`, `tool_executing`, `interrupted` or `error`.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEvent {
+    pub conversation_id: String,
+    pub event: serde_json::Value,
+}
+
+impl ServerEvent {
+    /// The event's `type` field, or an empty string if missing.
+    pub fn kind(&self) -> &str {
+        self.event
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+    }
+
+    /// A string field from the event payload.
+    pub fn str_field(&self, key: &str) -> Option<&str> {
+        self.event.get(key).and_then(|v| v.as_str())
+    }
+}
+
+/// Managed state tracking which conversation the bridge is subscribed to.
+#[derive(Default)]
+pub struct EventBridge {
+    conversation: Mutex<Option<String>>,
+    /// Session ID assigned by the server on connect, needed for
+    /// session-scoped endpoints such as interrupt.
+    session_id: Mutex<Option<String>>,
+    /// Bumped on every resubscribe so stale stream readers can exit.
+    generation: Arc<AtomicU64>,
+}
+
+impl EventBridge {
+    /// The conversation currently subscribed to, if any.
+    pub fn conversation(&self) -> Option<String> {
+        self.conversation
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+    }
+
+    /// The server session ID of the current subscription, if connected.
+    pub fn session_id(&self) -> Option<String> {
+        self.session_id.lock().ok().and_then(|guard| guard.clone())
+    }
+}
+
+/// Build a URL on the local gptme-server.
+pub fn server_url(path: &str) -> String {
+    format!("http://127.0.0.1:{}{}", crate::GPTME_SERVER_PORT, path)
+}
+
+/// Extract the conversation ID from a webui URL.
+///
+/// Supports both `/chat/<id>` routes and the `?conversation=<id>` query form.
+pub fn conversation_from_url(url: &url::Url) -> Option<String> {
+    let mut segments = url.path_segments()?;
+    if segments.next() == Some("chat") {
+        if let Some(id) = segments.next().filter(|id| !id.is_empty()) {
+            return Some(id.to_string());
+        }
+    }
+    url.query_pairs()
+        .find(|(key, _)| key == "conversation")
+        .map(|(_, value)| value.to_string())
+        .filter(|id| !id.is_empty())
+}
+
+/// The conversation the user is currently looking at: the most recently
+/// focused conversation window, or the active tab of the main window.
+pub fn active_conversation(app: &tauri::AppHandle) -> Option<String> {
+    let window = crate::windows::last_focused(app);
+    let label = if window == "main" {
+        crate::tabs::active_tab(app)
+    } else {
+        window
+    };
+    let webview = app.get_webview(&label)?;
+    conversation_from_url(&webview.url().ok()?)
+}
+
+/// Fan a server event out to the frontend and to Rust-side consumers.
+fn dispatch(app: &tauri::AppHandle, event: ServerEvent) {
+    if event.kind() == "connected" {
+        if let Ok(mut guard) = app.state::<EventBridge>().session_id.lock() {
+            *guard = event.str_field("session_id").map(str::to_string);
+        }
+    }
+
+    crate::pip::on_server_event(app, &event);
+    crate::status::on_server_event(app, &event);
+
+    if let Err(e) = app.emit("server-event", &event) {
+        log::warn!("Failed to emit server-event: {}", e);
+    }
+}
+
+/// Whether a read on the event stream failed only because it timed out.
+fn is_timeout(error: &io::Error) -> bool {
+    error
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<reqwest::Error>())
+        .is_some_and(reqwest::Error::is_timeout)
+}
+
+/// Read the SSE stream for a conversation until it goes stale.
+fn stream(app: tauri::AppHandle, conversation_id: String, generation: u64) {
+    let current = app.state::<EventBridge>().generation.clone();
+    let is_stale = || current.load(Ordering::SeqCst) != generation;
+
+    // Reads time out on a quiet stream, which is not an error; see `READ_TIMEOUT`
+    let client = match reqwest::blocking::Client::builder()
+        .timeout(READ_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            log::error!("Failed to create HTTP client for event bridge: {}", e);
+            return;
+        }
+    };
+    let url = server_url(&format!("/api/v2/conversations/{}/events", conversation_id));
+
+    while !is_stale() {
+        match client.get(&url).send() {
+            Ok(response) if response.status().is_success() => {
+                log::info!(
+                    "Event bridge subscribed to conversation {}",
+                    conversation_id
+                );
+                let mut reader = BufReader::new(response);
+                let mut line = String::new();
+                loop {
+                    // A timed-out read keeps any partial line in `line`
+                    match reader.read_line(&mut line) {
+                        Ok(0) => break,
+                        Ok(_) => {}
+                        Err(e) if is_timeout(&e) => {
+                            if is_stale() {
+                                return;
+                            }
+                            continue;
+                        }
+                        Err(_) => break,
+                    }
+                    if is_stale() {
+                        return;
+                    }
+                    if let Some(data) = line.trim_end().strip_prefix("data:") {
+                        match serde_json::from_str(data.trim()) {
+                            Ok(event) => dispatch(
+                                &app,
+                                ServerEvent {
+                                    conversation_id: conversation_id.clone(),
+                                    event,
+                                },
+                            ),
+                            Err(e) => log::warn!("Invalid server event: {}", e),
+                        }
+                    }
+                    line.clear();
+                }
+                log::info!("Event stream for {} closed", conversation_id);
+            }
+            Ok(response) => {
+                log::warn!(
+                    "Event stream for {} returned status {}",
+                    conversation_id,
+                    response.status()
+                );
+            }
+            Err(e) => {
+                log::debug!("Event stream for {} unavailable: {}", conversation_id, e);
+            }
+        }
+        std::thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+/// Start following the active conversation's server events.
+pub fn init(app: &tauri::AppHandle) {
+    app.manage(EventBridge::default());
+
+    let handle = app.clone();
+    std::thread::spawn(move || loop {
+        let active = active_conversation(&handle);
+        let bridge = handle.state::<EventBridge>();
+        let changed = match bridge.conversation.lock() {
+            Ok(mut guard) if *guard != active => {
+                *guard = active.clone();
+                true
+            }
+            _ => false,
+        };
+
+        if changed {
+            let generation = bridge.generation.fetch_add(1, Ordering::SeqCst) + 1;
+            if let Ok(mut guard) = bridge.session_id.lock() {
+                *guard = None;
+            }
+            if let Some(conversation_id) = active {
+                let app = handle.clone();
+                std::thread::spawn(move || stream(app, conversation_id, generation));
+            }
+            // Window menu entries are titled after their conversations
+            #[cfg(desktop)]
+            crate::menu::rebuild(&handle);
+        }
+
+        std::thread::sleep(WATCH_INTERVAL);
+    });
+}
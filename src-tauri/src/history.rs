@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::Manager;
+
+const HISTORY_FILE: &str = "quick_ask_history.json";
+
+/// Maximum number of quick-ask prompts kept; the oldest are dropped first.
+const CAPACITY: usize = 200;
+
+/// Managed ring buffer of quick-ask submissions, oldest first.
+pub struct HistoryState(Mutex<VecDeque<String>>);
+
+fn save(app: &tauri::AppHandle, entries: &VecDeque<String>) {
+    if let Err(e) = crate::config::save(app, HISTORY_FILE, entries) {
+        log::warn!("Failed to save quick-ask history: {}", e);
+    }
+}
+
+/// Record a submitted prompt, moving repeats to the front.
+pub fn push(app: &tauri::AppHandle, prompt: &str) {
+    let prompt = prompt.trim();
+    if prompt.is_empty() {
+        return;
+    }
+    let state = app.state::<HistoryState>();
+    let Ok(mut entries) = state.0.lock() else {
+        return;
+    };
+    entries.retain(|entry| entry != prompt);
+    entries.push_back(prompt.to_string());
+    while entries.len() > CAPACITY {
+        entries.pop_front();
+    }
+    save(app, &entries);
+}
+
+/// Prompts newest first, optionally filtered by a case-insensitive substring.
+pub fn search(app: &tauri::AppHandle, query: Option<&str>) -> Vec<String> {
+    let query = query.map(str::to_lowercase).filter(|q| !q.is_empty());
+    app.try_state::<HistoryState>()
+        .and_then(|state| {
+            state.0.lock().ok().map(|entries| {
+                entries
+                    .iter()
+                    .rev()
+                    .filter(|entry| match &query {
+                        Some(q) => entry.to_lowercase().contains(q),
+                        None => true,
+                    })
+                    .cloned()
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Load quick-ask history from the app config dir.
+pub fn init(app: &tauri::AppHandle) {
+    let mut entries: VecDeque<String> = crate::config::load(app, HISTORY_FILE);
+    while entries.len() > CAPACITY {
+        entries.pop_front();
+    }
+    app.manage(HistoryState(Mutex::new(entries)));
+}
+
+/// Get quick-ask history (newest first), optionally filtered by `query`.
+#[tauri::command]
+pub fn get_quick_ask_history(app: tauri::AppHandle, query: Option<String>) -> Vec<String> {
+    search(&app, query.as_deref())
+}
+
+/// Forget all quick-ask history.
+#[tauri::command]
+pub fn clear_quick_ask_history(app: tauri::AppHandle) -> Result<(), String> {
+    let state = app.state::<HistoryState>();
+    let mut entries = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    entries.clear();
+    crate::config::save(&app, HISTORY_FILE, &*entries)?;
+    log::info!("Cleared quick-ask history");
+    Ok(())
+}
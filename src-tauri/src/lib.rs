@@ -1,3 +1,26 @@
+mod abort;
+mod accessibility;
+#[cfg(desktop)]
+mod autostart;
+mod config;
+mod discord;
+mod events;
+mod history;
+#[cfg(desktop)]
+mod menu;
+mod pip;
+mod presentation;
+mod quick_ask;
+mod session;
+#[cfg(desktop)]
+mod shortcuts;
+mod status;
+mod tabs;
+mod templates;
+#[cfg(desktop)]
+mod tray;
+mod windows;
+
 use std::net::TcpListener;
 use std::sync::{Arc, Mutex};
 use tauri::Manager;
@@ -16,6 +39,54 @@ fn is_port_available(port: u16) -> bool {
     TcpListener::bind(format!("127.0.0.1:{}", port)).is_ok()
 }
 
+/// Build a webview source for a self-contained HTML page shipped with the app.
+///
+/// Used for small native windows that don't load the webui.
+fn inline_page(html: &str) -> Result<tauri::WebviewUrl, String> {
+    let encoded = percent_encoding::utf8_percent_encode(html, percent_encoding::NON_ALPHANUMERIC);
+    let url = format!("data:text/html;charset=utf-8,{}", encoded)
+        .parse::<url::Url>()
+        .map_err(|e| format!("Invalid inline page URL: {}", e))?;
+    Ok(tauri::WebviewUrl::External(url))
+}
+
+/// Build windows or webviews from a menu, tray or shortcut handler.
+///
+/// Those handlers run synchronously on the main thread, and building a window
+/// there deadlocks on Windows, so the work is handed to the async runtime.
+/// Commands that build windows are `async` for the same reason.
+fn spawn_window_task<T, F>(app: &tauri::AppHandle, action: &'static str, task: F)
+where
+    F: FnOnce(&tauri::AppHandle) -> Result<T, String> + Send + 'static,
+{
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = task(&app) {
+            log::error!("Failed to {}: {}", action, e);
+        }
+    });
+}
+
+/// Run a command and return its trimmed stdout, if it succeeded.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = std::process::Command::new(program);
+    command.args(args);
+
+    // Avoid flashing a console window for every call on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /// Managed state holding the gptme-server child process for cleanup on exit.
 struct ServerProcess(Arc<Mutex<Option<CommandChild>>>);
 
@@ -51,12 +122,41 @@ fn stop_server(state: tauri::State<'_, ServerProcess>) -> Result<(), String> {
     }
 }
 
+/// Terminate the gptme-server child process, if running. Used on shutdown.
+fn kill_server(app: &tauri::AppHandle) {
+    let arc = app.state::<ServerProcess>().0.clone();
+    let mut guard = match arc.lock() {
+        Ok(g) => g,
+        Err(_) => {
+            log::error!("Failed to acquire lock on server process");
+            return;
+        }
+    };
+    if let Some(child) = guard.take() {
+        log::info!("Terminating gptme-server process...");
+        match child.kill() {
+            Ok(_) => {
+                log::info!("gptme-server process terminated successfully");
+            }
+            Err(e) => {
+                log::error!("Failed to terminate gptme-server: {}", e);
+            }
+        }
+    } else {
+        log::warn!("No gptme-server process found to terminate");
+    }
+}
+
 /// Start the local gptme-server process (if not already running).
 #[tauri::command]
 async fn start_server(
     app: tauri::AppHandle,
     state: tauri::State<'_, ServerProcess>,
 ) -> Result<u16, String> {
+    // Any explicit start supersedes a start deferred by a hidden login launch
+    #[cfg(desktop)]
+    autostart::take_deferred_server(&app);
+
     // Check if already running
     {
         let guard = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
@@ -97,6 +197,7 @@ async fn start_server(
     );
 
     // Store child process
+    let pid = child.pid();
     {
         let mut guard = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
         *guard = Some(child);
@@ -133,9 +234,12 @@ async fn start_server(
                         "[gptme-server] Process terminated with code: {:?}",
                         payload.code
                     );
-                    // Clear state so get_server_status correctly reports not running
+                    // Clear state so get_server_status correctly reports not running,
+                    // unless a restart already stored a newer process
                     if let Ok(mut guard) = state_arc.lock() {
-                        *guard = None;
+                        if guard.as_ref().is_some_and(|child| child.pid() == pid) {
+                            *guard = None;
+                        }
                     }
                     break;
                 }
@@ -147,6 +251,54 @@ async fn start_server(
     Ok(GPTME_SERVER_PORT)
 }
 
+/// Restart the local gptme-server process.
+#[tauri::command]
+async fn restart_server(app: tauri::AppHandle) -> Result<u16, String> {
+    log::info!("Restarting gptme-server");
+    kill_server(&app);
+
+    // Give the old process a moment to release the port, without tying up
+    // an async worker while polling
+    tauri::async_runtime::spawn_blocking(|| {
+        for _ in 0..50 {
+            if is_port_available(GPTME_SERVER_PORT) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed waiting for port: {}", e))?;
+
+    start_server(app.clone(), app.state::<ServerProcess>()).await
+}
+
+/// Start gptme-server if a hidden login launch held it back, and wait until
+/// it accepts connections. Does nothing if the start wasn't deferred.
+///
+/// Anything that talks to the server calls this first, since an app hidden
+/// in the tray may never focus the window that would otherwise start it.
+/// Blocks, so call it off the main thread.
+fn ensure_server_started(app: &tauri::AppHandle) {
+    #[cfg(desktop)]
+    if autostart::take_deferred_server(app) {
+        log::info!("Starting deferred gptme-server");
+        let started =
+            tauri::async_runtime::block_on(start_server(app.clone(), app.state::<ServerProcess>()));
+        if let Err(e) = started {
+            log::error!("Failed to start deferred gptme-server: {}", e);
+            return;
+        }
+        // The sidecar can take a while to unpack and bind its port
+        for _ in 0..300 {
+            if !is_port_available(GPTME_SERVER_PORT) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+}
+
 /// Extract auth code from a gptme:// deep-link URL and inject it into the webview.
 ///
 /// Sets the URL hash to `#code=<hex>` and reloads the page, which triggers
@@ -170,7 +322,7 @@ fn handle_deep_link_urls(app: &tauri::AppHandle, urls: Vec<url::Url>) {
 
             log::info!("Auth code extracted from deep link, injecting into webview");
 
-            if let Some(window) = app.get_webview_window("main") {
+            if let Some(webview) = app.get_webview(tabs::MAIN_TAB) {
                 // Set URL hash with the auth code and reload the page.
                 // The webui's ApiContext checks window.location.hash on mount
                 // and automatically exchanges the code for a token via fleet.gptme.ai.
@@ -178,7 +330,7 @@ fn handle_deep_link_urls(app: &tauri::AppHandle, urls: Vec<url::Url>) {
                     "window.location.hash = '#code={}'; window.location.reload();",
                     safe_code
                 );
-                if let Err(e) = window.eval(&js) {
+                if let Err(e) = webview.eval(&js) {
                     log::error!("Failed to inject auth code into webview: {}", e);
                 }
             }
@@ -209,11 +361,14 @@ pub fn run() {
                 handle_deep_link_urls(app, urls);
             }
 
-            // Focus the main window when another instance tries to open
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.set_focus();
-            }
+            // Bring up the main window when another instance tries to open,
+            // including after a hidden login launch
+            tray::show_main_window(app);
         }));
+
+        builder = builder.plugin(tauri_plugin_global_shortcut::Builder::new().build());
+        builder = builder.plugin(autostart::plugin());
+        builder = builder.on_menu_event(menu::on_menu_event);
     }
 
     builder
@@ -236,10 +391,94 @@ pub fn run() {
             get_server_status,
             start_server,
             stop_server,
+            restart_server,
+            accessibility::get_accessibility_preferences,
+            presentation::enter_presentation_mode,
+            presentation::exit_presentation_mode,
+            presentation::get_presentation_mode,
+            pip::show_pip_window,
+            pip::dismiss_pip_window,
+            pip::dock_pip_window,
+            tabs::create_tab,
+            tabs::close_tab,
+            tabs::activate_tab,
+            tabs::reorder_tabs,
+            tabs::list_tabs,
+            windows::open_conversation_window,
+            windows::list_conversation_windows,
+            status::get_activity_status,
+            discord::get_discord_settings,
+            discord::set_discord_settings,
+            templates::list_templates,
+            templates::save_template,
+            templates::delete_template,
+            templates::render_template,
+            templates::use_template,
+            quick_ask::show_quick_ask,
+            quick_ask::hide_quick_ask,
+            history::get_quick_ask_history,
+            history::clear_quick_ask_history,
+            #[cfg(desktop)]
+            shortcuts::get_shortcuts,
+            #[cfg(desktop)]
+            shortcuts::set_shortcut,
+            #[cfg(desktop)]
+            shortcuts::reset_shortcuts,
+            #[cfg(desktop)]
+            autostart::get_autostart_settings,
+            #[cfg(desktop)]
+            autostart::set_autostart_enabled,
+            #[cfg(desktop)]
+            autostart::set_autostart_settings,
+            abort::abort_all,
         ])
+        .manage(presentation::PresentationState::default())
+        .manage(pip::PipState::default())
+        .manage(tabs::TabState::default())
+        .manage(windows::WindowRegistry::default())
+        .manage(status::StatusState::default())
+        .manage(quick_ask::QuickAskState::default())
+        .on_page_load(|webview, payload| {
+            accessibility::on_page_load(webview, payload);
+            presentation::on_page_load(webview, payload);
+            pip::on_page_load(webview, payload);
+            quick_ask::on_page_load(webview, payload);
+        })
         .setup(|app| {
             log::info!("Starting gptme-tauri application");
 
+            // Detect OS accessibility preferences and watch for changes
+            accessibility::init(app.handle());
+
+            // Follow the active conversation's server events
+            events::init(app.handle());
+
+            // Opt-in Discord Rich Presence
+            discord::init(app.handle());
+
+            // Prompt templates and the global quick-ask popup
+            templates::init(app.handle());
+            history::init(app.handle());
+            #[cfg(desktop)]
+            shortcuts::init(app.handle());
+
+            // Native Window menu listing open tabs and windows
+            #[cfg(desktop)]
+            menu::rebuild(app.handle());
+
+            // Tray icon showing live activity in its tooltip
+            #[cfg(desktop)]
+            tray::init(app.handle())?;
+
+            // Launch at login, optionally hidden to the tray with a lazy server start
+            #[cfg(desktop)]
+            let defer_server = autostart::init(app.handle());
+            #[cfg(not(desktop))]
+            let defer_server = false;
+
+            // Reopen last session's conversations once the server is up
+            session::init(app.handle());
+
             // Register deep-link schemes at runtime (needed for dev on Linux/Windows)
             #[cfg(desktop)]
             if cfg!(debug_assertions) {
@@ -276,6 +515,11 @@ pub fn run() {
 
             // Spawn gptme-server with output capture
             tauri::async_runtime::spawn(async move {
+                if defer_server {
+                    log::info!("Deferring gptme-server start until the window is shown");
+                    return;
+                }
+
                 // Check if port is available before starting
                 if !is_port_available(GPTME_SERVER_PORT) {
                     log::error!(
@@ -329,6 +573,7 @@ pub fn run() {
                         );
 
                         // Store child process for later cleanup
+                        let pid = child.pid();
                         if let Ok(mut guard) = child_for_spawn.lock() {
                             *guard = Some(child);
                         }
@@ -366,9 +611,15 @@ pub fn run() {
                                             "[gptme-server] Process terminated with code: {:?}",
                                             payload.code
                                         );
-                                        // Clear state so get_server_status correctly reports not running
+                                        // Clear state so get_server_status correctly reports not running,
+                                        // unless a restart already stored a newer process
                                         if let Ok(mut guard) = child_for_output.lock() {
-                                            *guard = None;
+                                            if guard
+                                                .as_ref()
+                                                .is_some_and(|child| child.pid() == pid)
+                                            {
+                                                *guard = None;
+                                            }
                                         }
                                         break;
                                     }
@@ -386,32 +637,36 @@ pub fn run() {
             Ok(())
         })
         .on_window_event(|window, event| {
-            if let tauri::WindowEvent::CloseRequested { .. } = event {
-                log::info!("Window close requested, cleaning up gptme-server...");
-
-                let arc = window.state::<ServerProcess>().0.clone();
-                let mut guard = match arc.lock() {
-                    Ok(g) => g,
-                    Err(_) => {
-                        log::error!("Failed to acquire lock on server process");
-                        return;
-                    }
-                };
-                if let Some(child) = guard.take() {
-                    log::info!("Terminating gptme-server process...");
-                    match child.kill() {
-                        Ok(_) => {
-                            log::info!("gptme-server process terminated successfully");
-                        }
-                        Err(e) => {
-                            log::error!("Failed to terminate gptme-server: {}", e);
-                        }
-                    }
-                } else {
-                    log::warn!("No gptme-server process found to terminate");
-                }
+            windows::on_window_event(window, event);
+            quick_ask::on_window_event(window, event);
+            #[cfg(desktop)]
+            autostart::on_window_event(window, event);
+
+            // Only the main window owns the server; auxiliary windows just close
+            if window.label() != "main" {
+                return;
+            }
+
+            // Closing the main window quits the app, so conversation windows,
+            // PiP and quick-ask don't linger against a stopped server. The
+            // window stays open until the exit handler has saved the session.
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                log::info!("Main window close requested, quitting");
+                api.prevent_close();
+                window.app_handle().exit(0);
             }
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // Every way of quitting (tray, app menu, Cmd+Q, closing the main
+            // window) ends up here
+            tauri::RunEvent::ExitRequested { .. } => session::save(app),
+            tauri::RunEvent::Exit => {
+                session::save(app);
+                log::info!("Exiting, cleaning up gptme-server...");
+                kill_server(app);
+            }
+            _ => {}
+        });
 }
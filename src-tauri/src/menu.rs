@@ -0,0 +1,238 @@
+use tauri::menu::{
+    CheckMenuItemBuilder, Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder,
+};
+use tauri::Manager;
+
+use crate::shortcuts::{self, Action};
+
+const NEXT_ID: &str = "window-next";
+const PREVIOUS_ID: &str = "window-previous";
+/// Fixed accelerators for cycling conversations; user shortcuts may not use them.
+pub const NEXT_ACCELERATOR: &str = "CmdOrCtrl+Shift+]";
+pub const PREVIOUS_ACCELERATOR: &str = "CmdOrCtrl+Shift+[";
+/// Menu item IDs for shortcut actions are `action:<action>`.
+const ACTION_PREFIX: &str = "action:";
+/// Menu item IDs for tab entries are `tab:<webview label>`.
+const TAB_PREFIX: &str = "tab:";
+/// Menu item IDs for window entries are `window:<window label>`.
+const WINDOW_PREFIX: &str = "window:";
+
+/// A conversation tab or window listed in the Window menu.
+struct Entry {
+    id: String,
+    title: String,
+    active: bool,
+}
+
+fn title_for(conversation_id: Option<String>) -> String {
+    conversation_id.unwrap_or_else(|| "New conversation".to_string())
+}
+
+/// Tabs of the main window followed by the other conversation windows.
+fn entries(app: &tauri::AppHandle) -> Vec<Entry> {
+    let focused = crate::windows::last_focused(app);
+    let mut entries = Vec::new();
+
+    if let Ok(tabs) = crate::tabs::snapshot(app) {
+        for tab in tabs.tabs {
+            entries.push(Entry {
+                active: focused == "main" && tab.id == tabs.active,
+                id: format!("{}{}", TAB_PREFIX, tab.id),
+                title: title_for(tab.conversation_id),
+            });
+        }
+    }
+
+    for window in crate::windows::list(app) {
+        if window.label == "main" {
+            continue;
+        }
+        entries.push(Entry {
+            active: window.label == focused,
+            id: format!("{}{}", WINDOW_PREFIX, window.label),
+            title: title_for(window.conversation_id),
+        });
+    }
+
+    entries
+}
+
+/// Accelerator for the Window menu entry at `index`; only the first nine
+/// entries get one.
+pub fn entry_accelerator(index: usize) -> Option<String> {
+    (index < 9).then(|| format!("CmdOrCtrl+{}", index + 1))
+}
+
+fn action_id(action: Action) -> String {
+    format!("{}{:?}", ACTION_PREFIX, action)
+}
+
+/// A menu item for a shortcut action. Global actions are registered with the
+/// OS, so their accelerator isn't repeated on the menu item.
+fn action_item(
+    app: &tauri::AppHandle,
+    action: Action,
+) -> tauri::Result<tauri::menu::MenuItem<tauri::Wry>> {
+    let mut item = MenuItemBuilder::with_id(action_id(action), action.label());
+    if !action.is_global() {
+        if let Some(accelerator) = shortcuts::accelerator(app, action) {
+            item = item.accelerator(accelerator);
+        }
+    }
+    item.build(app)
+}
+
+fn build(app: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let file_menu = SubmenuBuilder::new(app, "File")
+        .item(&action_item(app, Action::NewConversation)?)
+        .item(&action_item(app, Action::QuickAsk)?)
+        .separator()
+        .item(&action_item(app, Action::AbortAll)?)
+        .item(&action_item(app, Action::RestartServer)?)
+        .build()?;
+
+    let mut window_menu = SubmenuBuilder::new(app, "Window")
+        .minimize()
+        .separator()
+        .item(
+            &MenuItemBuilder::with_id(NEXT_ID, "Next Conversation")
+                .accelerator(NEXT_ACCELERATOR)
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::with_id(PREVIOUS_ID, "Previous Conversation")
+                .accelerator(PREVIOUS_ACCELERATOR)
+                .build(app)?,
+        )
+        .separator();
+
+    for (index, entry) in entries(app).into_iter().enumerate() {
+        let mut item = CheckMenuItemBuilder::with_id(entry.id, entry.title).checked(entry.active);
+        if let Some(accelerator) = entry_accelerator(index) {
+            item = item.accelerator(accelerator);
+        }
+        window_menu = window_menu.item(&item.build(app)?);
+    }
+    let window_menu = window_menu.build()?;
+
+    // Let macOS append its own window list and tab commands to this menu
+    #[cfg(target_os = "macos")]
+    window_menu.set_as_windows_menu_for_nsapp()?;
+
+    let edit_menu = SubmenuBuilder::new(app, "Edit")
+        .undo()
+        .redo()
+        .separator()
+        .cut()
+        .copy()
+        .paste()
+        .select_all()
+        .build()?;
+
+    let menu = MenuBuilder::new(app);
+    #[cfg(target_os = "macos")]
+    let menu = menu.item(
+        &SubmenuBuilder::new(app, app.package_info().name.clone())
+            .about(None)
+            .separator()
+            .services()
+            .separator()
+            .hide()
+            .hide_others()
+            .show_all()
+            .separator()
+            .quit()
+            .build()?,
+    );
+    menu.item(&file_menu)
+        .item(&edit_menu)
+        .item(&window_menu)
+        .build()
+}
+
+/// Install the menu: app-wide on macOS, per conversation window elsewhere
+/// so auxiliary windows (e.g. picture-in-picture) and a window in
+/// presentation mode don't get a menu bar.
+fn install(app: &tauri::AppHandle, menu: Menu<tauri::Wry>) -> tauri::Result<()> {
+    #[cfg(target_os = "macos")]
+    app.set_menu(menu)?;
+
+    #[cfg(not(target_os = "macos"))]
+    let presented = crate::presentation::presented_window(app);
+    #[cfg(not(target_os = "macos"))]
+    for label in crate::windows::labels(app) {
+        if presented.as_deref() == Some(label.as_str()) {
+            continue;
+        }
+        if let Some(window) = app.get_window(&label) {
+            window.set_menu(menu.clone())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rebuild the menu so the Window menu reflects open tabs and windows.
+pub fn rebuild(app: &tauri::AppHandle) {
+    if let Err(e) = build(app).and_then(|menu| install(app, menu)) {
+        log::warn!("Failed to rebuild Window menu: {}", e);
+    }
+}
+
+/// Focus a tab or window by its menu item ID.
+fn focus(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    if let Some(tab) = id.strip_prefix(TAB_PREFIX) {
+        if let Some(window) = app.get_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        crate::tabs::activate(app, tab)
+    } else if let Some(label) = id.strip_prefix(WINDOW_PREFIX) {
+        let window = app
+            .get_window(label)
+            .ok_or_else(|| format!("No such window: {}", label))?;
+        window.set_focus().map_err(|e| e.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Move focus to the next (or previous) tab/window in menu order.
+fn cycle(app: &tauri::AppHandle, forward: bool) -> Result<(), String> {
+    let entries = entries(app);
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let current = entries.iter().position(|e| e.active).unwrap_or(0);
+    let next = if forward {
+        (current + 1) % entries.len()
+    } else {
+        (current + entries.len() - 1) % entries.len()
+    };
+    focus(app, &entries[next].id)
+}
+
+/// Handle clicks on File and Window menu items.
+pub fn on_menu_event(app: &tauri::AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref();
+    if let Some(action) = id
+        .strip_prefix(ACTION_PREFIX)
+        .and_then(|name| Action::ALL.into_iter().find(|a| format!("{:?}", a) == name))
+    {
+        shortcuts::run(app, action);
+        return;
+    }
+
+    let result = match id {
+        NEXT_ID => cycle(app, true),
+        PREVIOUS_ID => cycle(app, false),
+        _ if id.starts_with(TAB_PREFIX) || id.starts_with(WINDOW_PREFIX) => focus(app, id),
+        // Other menus (e.g. the tray) handle their own items
+        _ => return,
+    };
+    if let Err(e) = result {
+        log::warn!("Window menu action '{}' failed: {}", id, e);
+    }
+    // Check marks toggle on click, so re-sync them with the real focus
+    rebuild(app);
+}
@@ -0,0 +1,185 @@
+use std::sync::Mutex;
+use tauri::{Manager, PhysicalPosition, WebviewWindowBuilder};
+
+use crate::events::ServerEvent;
+
+const PIP_LABEL: &str = "pip";
+const PIP_HTML: &str = include_str!("../pages/pip.html");
+const PIP_WIDTH: f64 = 360.0;
+const PIP_HEIGHT: f64 = 220.0;
+/// Logical gap between a docked window and the screen edge.
+const DOCK_MARGIN: f64 = 16.0;
+
+/// Screen corner the picture-in-picture window can be docked to.
+#[derive(Clone, Copy, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Mirror of the currently streaming response, kept even while the window
+/// is closed so it can be replayed when the window is (re)opened.
+#[derive(Default)]
+struct PipContent {
+    text: String,
+    status: String,
+}
+
+/// Managed state for the picture-in-picture window.
+#[derive(Default)]
+pub struct PipState(Mutex<PipContent>);
+
+/// Run a script against the PiP page, if the window is open.
+fn eval(app: &tauri::AppHandle, js: &str) {
+    if let Some(window) = app.get_webview_window(PIP_LABEL) {
+        if let Err(e) = window.eval(js) {
+            log::warn!("Failed to update picture-in-picture window: {}", e);
+        }
+    }
+}
+
+fn set_status(app: &tauri::AppHandle, content: &mut PipContent, status: String) {
+    let js = format!(
+        "window.__pip && window.__pip.status({});",
+        serde_json::to_string(&status).unwrap_or_default()
+    );
+    content.status = status;
+    eval(app, &js);
+}
+
+/// Mirror streaming output from the event bridge into the PiP window.
+pub fn on_server_event(app: &tauri::AppHandle, event: &ServerEvent) {
+    let state = app.state::<PipState>();
+    let Ok(mut content) = state.0.lock() else {
+        return;
+    };
+
+    match event.kind() {
+        "generation_started" => {
+            content.text.clear();
+            content.status = "Generating…".to_string();
+            replay(app, &content);
+        }
+        "generation_progress" => {
+            if let Some(token) = event.str_field("token") {
+                content.text.push_str(token);
+                let js = format!(
+                    "window.__pip && window.__pip.append({});",
+                    serde_json::to_string(token).unwrap_or_default()
+                );
+                eval(app, &js);
+            }
+        }
+        "generation_complete" => set_status(app, &mut content, "Done".to_string()),
+        "tool_pending" => {
+            let tool = event
+                .event
+                .pointer("/tooluse/tool")
+                .and_then(|v| v.as_str())
+                .unwrap_or("tool");
+            set_status(app, &mut content, format!("Waiting to run {}", tool));
+        }
+        "tool_executing" => set_status(app, &mut content, "Running tool…".to_string()),
+        "interrupted" => set_status(app, &mut content, "Interrupted".to_string()),
+        "error" => {
+            let error = event.str_field("error").unwrap_or("unknown error");
+            set_status(app, &mut content, format!("Error: {}", error));
+        }
+        _ => {}
+    }
+}
+
+/// Push the full buffered response into the PiP page.
+fn replay(app: &tauri::AppHandle, content: &PipContent) {
+    let js = format!(
+        "window.__pip && window.__pip.set({}, {});",
+        serde_json::to_string(&content.text).unwrap_or_default(),
+        serde_json::to_string(&content.status).unwrap_or_default()
+    );
+    eval(app, &js);
+}
+
+/// Replay the buffered response once the PiP page has loaded.
+pub fn on_page_load(webview: &tauri::Webview, payload: &tauri::webview::PageLoadPayload<'_>) {
+    if webview.label() != PIP_LABEL || payload.event() != tauri::webview::PageLoadEvent::Finished {
+        return;
+    }
+    let app = webview.app_handle();
+    if let Ok(content) = app.state::<PipState>().0.lock() {
+        replay(app, &content);
+    }
+}
+
+/// Move the PiP window to a corner of its current monitor.
+fn dock(app: &tauri::AppHandle, corner: Corner) -> Result<(), String> {
+    let window = app
+        .get_webview_window(PIP_LABEL)
+        .ok_or_else(|| "Picture-in-picture window is not open".to_string())?;
+    let monitor = window
+        .current_monitor()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No monitor found for picture-in-picture window".to_string())?;
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+    let margin = (DOCK_MARGIN * monitor.scale_factor()) as i32;
+
+    let left = monitor.position().x + margin;
+    let top = monitor.position().y + margin;
+    let right = monitor.position().x + monitor.size().width as i32 - size.width as i32 - margin;
+    let bottom = monitor.position().y + monitor.size().height as i32 - size.height as i32 - margin;
+
+    let (x, y) = match corner {
+        Corner::TopLeft => (left, top),
+        Corner::TopRight => (right, top),
+        Corner::BottomLeft => (left, bottom),
+        Corner::BottomRight => (right, bottom),
+    };
+    window
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(|e| format!("Failed to dock picture-in-picture window: {}", e))
+}
+
+/// Open the always-on-top PiP window without stealing focus.
+fn show(app: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(PIP_LABEL) {
+        return window.show().map_err(|e| e.to_string());
+    }
+
+    WebviewWindowBuilder::new(app, PIP_LABEL, crate::inline_page(PIP_HTML)?)
+        .title("gptme")
+        .inner_size(PIP_WIDTH, PIP_HEIGHT)
+        .always_on_top(true)
+        .visible_on_all_workspaces(true)
+        .skip_taskbar(true)
+        .focused(false)
+        .build()
+        .map_err(|e| format!("Failed to open picture-in-picture window: {}", e))?;
+    log::info!("Opened picture-in-picture window");
+
+    dock(app, Corner::default())
+}
+
+/// Open the picture-in-picture response window.
+#[tauri::command]
+pub async fn show_pip_window(app: tauri::AppHandle) -> Result<(), String> {
+    show(&app)
+}
+
+/// Close the picture-in-picture response window.
+#[tauri::command]
+pub fn dismiss_pip_window(app: tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(PIP_LABEL) {
+        window.close().map_err(|e| e.to_string())?;
+        log::info!("Closed picture-in-picture window");
+    }
+    Ok(())
+}
+
+/// Dock the picture-in-picture window to a screen corner (default: bottom-right).
+#[tauri::command]
+pub fn dock_pip_window(app: tauri::AppHandle, corner: Option<Corner>) -> Result<(), String> {
+    dock(&app, corner.unwrap_or_default())
+}
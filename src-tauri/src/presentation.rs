@@ -0,0 +1,267 @@
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+#[cfg(desktop)]
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+/// Global shortcut that always leaves presentation mode, even when in-page
+/// shortcuts are disabled or the input box is hidden.
+pub const EXIT_SHORTCUT: &str = "CommandOrControl+Shift+F11";
+
+/// Options for presentation (kiosk) mode.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentationOptions {
+    /// Hide the chat input box so only the conversation is visible.
+    #[serde(default)]
+    pub hide_input: bool,
+    /// Swallow keyboard shortcuts inside the webview.
+    #[serde(default)]
+    pub disable_shortcuts: bool,
+}
+
+/// Window state captured on entry so it can be restored on exit.
+struct ActivePresentation {
+    window_label: String,
+    /// The webview (main window tab or conversation window) being presented.
+    webview_label: String,
+    options: PresentationOptions,
+    was_fullscreen: bool,
+    was_decorated: bool,
+}
+
+/// Managed state for the currently active presentation, if any.
+#[derive(Default)]
+pub struct PresentationState(Mutex<Option<ActivePresentation>>);
+
+/// Build the script that hides the input box and/or blocks shortcuts.
+fn enter_script(options: &PresentationOptions) -> String {
+    format!(
+        r#"(function() {{
+  if (window.__gptmePresentation) return;
+  var style = document.createElement("style");
+  style.id = "gptme-presentation-style";
+  style.textContent = {hide_input} ?
+    "form:has(textarea), [data-chat-input] {{ display: none !important; }}" :
+    "";
+  document.head.appendChild(style);
+  var blocker = function(e) {{
+    if (e.ctrlKey || e.metaKey || e.altKey || /^F\d+$/.test(e.key)) {{
+      e.preventDefault();
+      e.stopImmediatePropagation();
+    }}
+  }};
+  if ({disable_shortcuts}) window.addEventListener("keydown", blocker, true);
+  window.__gptmePresentation = {{ style: style, blocker: blocker }};
+}})();"#,
+        hide_input = options.hide_input,
+        disable_shortcuts = options.disable_shortcuts,
+    )
+}
+
+const EXIT_SCRIPT: &str = r#"(function() {
+  var p = window.__gptmePresentation;
+  if (!p) return;
+  p.style.remove();
+  window.removeEventListener("keydown", p.blocker, true);
+  delete window.__gptmePresentation;
+})();"#;
+
+fn emit_changed(app: &tauri::AppHandle, options: Option<&PresentationOptions>) {
+    if let Err(e) = app.emit("presentation-mode-changed", options) {
+        log::warn!("Failed to emit presentation-mode-changed event: {}", e);
+    }
+}
+
+/// Switch a webview's window into fullscreen, chrome-less presentation mode.
+fn enter(
+    app: &tauri::AppHandle,
+    webview: &tauri::Webview,
+    options: PresentationOptions,
+) -> Result<(), String> {
+    let window = webview.window();
+    let state = app.state::<PresentationState>();
+    let mut guard = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if guard.is_some() {
+        return Err("Presentation mode is already active".to_string());
+    }
+
+    let was_fullscreen = window.is_fullscreen().map_err(|e| e.to_string())?;
+    let was_decorated = window.is_decorated().map_err(|e| e.to_string())?;
+
+    // Register the escape hatch first, so a failure leaves the window untouched
+    #[cfg(desktop)]
+    app.global_shortcut()
+        .on_shortcut(EXIT_SHORTCUT, |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                log::info!("Presentation exit shortcut pressed");
+                if let Err(e) = exit(app) {
+                    log::error!("Failed to exit presentation mode: {}", e);
+                }
+            }
+        })
+        .map_err(|e| format!("Failed to register presentation exit shortcut: {}", e))?;
+
+    let result = window
+        .set_decorations(false)
+        .map_err(|e| format!("Failed to hide window decorations: {}", e))
+        .and_then(|()| {
+            window
+                .set_fullscreen(true)
+                .map_err(|e| format!("Failed to enter fullscreen: {}", e))
+        });
+    if let Err(e) = result {
+        let _ = window.set_fullscreen(was_fullscreen);
+        let _ = window.set_decorations(was_decorated);
+        #[cfg(desktop)]
+        let _ = app.global_shortcut().unregister(EXIT_SHORTCUT);
+        return Err(e);
+    }
+
+    // The menu bar is per window outside macOS; `menu::install` skips the
+    // presented window until it exits
+    #[cfg(desktop)]
+    if let Err(e) = window.hide_menu() {
+        log::warn!("Failed to hide menu bar: {}", e);
+    }
+
+    if let Err(e) = webview.eval(&enter_script(&options)) {
+        log::warn!("Failed to inject presentation script: {}", e);
+    }
+
+    log::info!(
+        "Entered presentation mode on '{}' ({:?})",
+        webview.label(),
+        options
+    );
+    emit_changed(app, Some(&options));
+
+    *guard = Some(ActivePresentation {
+        window_label: window.label().to_string(),
+        webview_label: webview.label().to_string(),
+        options,
+        was_fullscreen,
+        was_decorated,
+    });
+    Ok(())
+}
+
+/// Leave presentation mode and restore the window to its previous state.
+pub fn exit(app: &tauri::AppHandle) -> Result<(), String> {
+    let active = {
+        let state = app.state::<PresentationState>();
+        let mut guard = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        guard
+            .take()
+            .ok_or_else(|| "Presentation mode is not active".to_string())?
+    };
+
+    #[cfg(desktop)]
+    if let Err(e) = app.global_shortcut().unregister(EXIT_SHORTCUT) {
+        log::warn!("Failed to unregister presentation exit shortcut: {}", e);
+    }
+
+    if let Some(webview) = app.get_webview(&active.webview_label) {
+        if let Err(e) = webview.eval(EXIT_SCRIPT) {
+            log::warn!("Failed to remove presentation script: {}", e);
+        }
+    }
+    if let Some(window) = app.get_window(&active.window_label) {
+        window
+            .set_fullscreen(active.was_fullscreen)
+            .map_err(|e| format!("Failed to leave fullscreen: {}", e))?;
+        window
+            .set_decorations(active.was_decorated)
+            .map_err(|e| format!("Failed to restore window decorations: {}", e))?;
+        // Pick up any menu changes made while presenting
+        #[cfg(desktop)]
+        {
+            crate::menu::rebuild(app);
+            if let Err(e) = window.show_menu() {
+                log::warn!("Failed to show menu bar: {}", e);
+            }
+        }
+        let _ = window.set_focus();
+    }
+
+    log::info!("Exited presentation mode");
+    emit_changed(app, None);
+    Ok(())
+}
+
+/// Label of the window in presentation mode, if any.
+#[cfg(all(desktop, not(target_os = "macos")))]
+pub fn presented_window(app: &tauri::AppHandle) -> Option<String> {
+    app.try_state::<PresentationState>().and_then(|state| {
+        state
+            .0
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|active| active.window_label.clone()))
+    })
+}
+
+/// Whether app shortcuts should be ignored because a presentation with
+/// `disable_shortcuts` is running. The exit shortcut is unaffected.
+#[cfg(desktop)]
+pub fn shortcuts_disabled(app: &tauri::AppHandle) -> bool {
+    app.try_state::<PresentationState>()
+        .and_then(|state| {
+            state.0.lock().ok().map(|guard| {
+                guard
+                    .as_ref()
+                    .is_some_and(|active| active.options.disable_shortcuts)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Re-inject the presentation script if the presenting webview reloads.
+pub fn on_page_load(webview: &tauri::Webview, payload: &tauri::webview::PageLoadPayload<'_>) {
+    if payload.event() != tauri::webview::PageLoadEvent::Finished {
+        return;
+    }
+    let Some(state) = webview.try_state::<PresentationState>() else {
+        return;
+    };
+    let script = match state.0.lock() {
+        Ok(guard) => match guard.as_ref() {
+            Some(active) if active.webview_label == webview.label() => {
+                enter_script(&active.options)
+            }
+            _ => return,
+        },
+        Err(_) => return,
+    };
+    if let Err(e) = webview.eval(&script) {
+        log::warn!("Failed to re-inject presentation script: {}", e);
+    }
+}
+
+/// Enter fullscreen, chrome-less presentation mode on the calling webview's
+/// window (the main window when called from a tab).
+#[tauri::command]
+pub fn enter_presentation_mode(
+    app: tauri::AppHandle,
+    webview: tauri::Webview,
+    options: Option<PresentationOptions>,
+) -> Result<(), String> {
+    enter(&app, &webview, options.unwrap_or_default())
+}
+
+/// Leave presentation mode.
+#[tauri::command]
+pub fn exit_presentation_mode(app: tauri::AppHandle) -> Result<(), String> {
+    exit(&app)
+}
+
+/// Get the active presentation options, or `None` when not presenting.
+#[tauri::command]
+pub fn get_presentation_mode(
+    state: tauri::State<'_, PresentationState>,
+) -> Option<PresentationOptions> {
+    state
+        .0
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|active| active.options.clone()))
+}
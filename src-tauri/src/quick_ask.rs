@@ -0,0 +1,221 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{Emitter, Manager, WebviewWindowBuilder};
+
+const QUICK_ASK_LABEL: &str = "quick-ask";
+const QUICK_ASK_HTML: &str = include_str!("../pages/quick-ask.html");
+
+/// The quick-ask page talks to Rust by navigating to this (unresolvable)
+/// host; `on_navigation` intercepts and cancels those requests.
+const MESSAGE_HOST: &str = "quick-ask.invalid";
+
+/// Submissions so far, used to make conversation IDs unique.
+static SUBMIT_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Managed state holding a prefill waiting for the page to finish loading.
+#[derive(Default)]
+pub struct QuickAskState(Mutex<Option<String>>);
+
+/// Script that (re)opens the prompt with an optional prefill, the current
+/// template list and the prompt history for Up/Down recall.
+fn open_script(app: &tauri::AppHandle, prefill: Option<&str>) -> String {
+    let templates: Vec<serde_json::Value> = crate::templates::list(app)
+        .into_iter()
+        .map(|t| serde_json::json!({ "name": t.name, "body": t.body }))
+        .collect();
+    let history = crate::history::search(app, None);
+    format!(
+        "window.__quickAsk && window.__quickAsk.open({}, {}, {});",
+        serde_json::to_string(&prefill).unwrap_or_else(|_| "null".to_string()),
+        serde_json::to_string(&templates).unwrap_or_else(|_| "[]".to_string()),
+        serde_json::to_string(&history).unwrap_or_else(|_| "[]".to_string())
+    )
+}
+
+fn hide(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ASK_LABEL) {
+        let _ = window.hide();
+    }
+}
+
+/// Show the quick-ask window, optionally prefilled (e.g. from a template).
+pub fn show(app: &tauri::AppHandle, prefill: Option<String>) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(QUICK_ASK_LABEL) {
+        window.show().map_err(|e| e.to_string())?;
+        let _ = window.set_focus();
+        return window
+            .eval(&open_script(app, prefill.as_deref()))
+            .map_err(|e| e.to_string());
+    }
+
+    // The page can't take the prefill until it has loaded; see `on_page_load`
+    if let Ok(mut pending) = app.state::<QuickAskState>().0.lock() {
+        *pending = prefill;
+    }
+
+    let handle = app.clone();
+    WebviewWindowBuilder::new(app, QUICK_ASK_LABEL, crate::inline_page(QUICK_ASK_HTML)?)
+        .title("Quick Ask")
+        .inner_size(600.0, 160.0)
+        .center()
+        .decorations(false)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .on_navigation(move |url| on_navigation(&handle, url))
+        .build()
+        .map_err(|e| format!("Failed to open quick-ask window: {}", e))?;
+    log::info!("Opened quick-ask window");
+    Ok(())
+}
+
+/// Show the quick-ask window, or hide it if it is already focused.
+pub fn toggle(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window(QUICK_ASK_LABEL) {
+        if window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false) {
+            let _ = window.hide();
+            return;
+        }
+    }
+    crate::spawn_window_task(app, "show quick-ask window", |app| show(app, None));
+}
+
+/// Handle messages from the quick-ask page; allow only the initial page load.
+fn on_navigation(app: &tauri::AppHandle, url: &url::Url) -> bool {
+    if url.host_str() != Some(MESSAGE_HOST) {
+        return url.scheme() == "data";
+    }
+
+    match url.path() {
+        "/submit" => {
+            let prompt = url
+                .query_pairs()
+                .find(|(key, _)| key == "prompt")
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default();
+            let app = app.clone();
+            // Talking to the server blocks, so keep it off the main thread
+            std::thread::spawn(move || submit(&app, prompt));
+        }
+        "/cancel" => hide(app),
+        other => log::warn!("Unknown quick-ask message: {}", other),
+    }
+    false
+}
+
+/// Create a conversation on the local server seeded with the prompt.
+fn create_conversation(prompt: &str) -> Result<String, String> {
+    // The counter keeps IDs unique even if the clock doesn't advance between submits
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let count = SUBMIT_COUNT.fetch_add(1, Ordering::Relaxed);
+    let conversation_id = format!("quick-ask-{:x}-{}", nanos, count);
+    let body = serde_json::json!({
+        "messages": [{ "role": "user", "content": prompt }],
+    });
+
+    let response = reqwest::blocking::Client::new()
+        .put(crate::events::server_url(&format!(
+            "/api/v2/conversations/{}",
+            conversation_id
+        )))
+        .json(&body)
+        .send()
+        .map_err(|e| format!("Could not reach gptme-server: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("gptme-server returned {}", response.status()));
+    }
+    Ok(conversation_id)
+}
+
+/// Show a conversation in the main window's active tab.
+fn open_conversation(app: &tauri::AppHandle, conversation_id: &str) -> Result<(), String> {
+    let webview = app
+        .get_webview(&crate::tabs::active_tab(app))
+        .ok_or_else(|| "Main window not found".to_string())?;
+    let url = webview
+        .url()
+        .map_err(|e| e.to_string())?
+        .join(&format!("/chat/{}", conversation_id))
+        .map_err(|e| e.to_string())?;
+    webview.navigate(url).map_err(|e| e.to_string())?;
+    #[cfg(desktop)]
+    crate::tray::show_main_window(app);
+    Ok(())
+}
+
+fn submit(app: &tauri::AppHandle, prompt: String) {
+    log::info!("Quick-ask submitted ({} chars)", prompt.len());
+    crate::history::push(app, &prompt);
+    crate::ensure_server_started(app);
+    let result = create_conversation(&prompt).and_then(|conversation_id| {
+        open_conversation(app, &conversation_id)?;
+        Ok(conversation_id)
+    });
+
+    match result {
+        Ok(conversation_id) => {
+            hide(app);
+            let payload = serde_json::json!({
+                "prompt": prompt,
+                "conversationId": conversation_id,
+            });
+            if let Err(e) = app.emit("quick-ask-submitted", payload) {
+                log::warn!("Failed to emit quick-ask-submitted event: {}", e);
+            }
+        }
+        Err(e) => {
+            log::error!("Quick-ask failed: {}", e);
+            if let Some(window) = app.get_webview_window(QUICK_ASK_LABEL) {
+                let js = format!(
+                    "window.__quickAsk && window.__quickAsk.error({});",
+                    serde_json::to_string(&e).unwrap_or_default()
+                );
+                let _ = window.eval(&js);
+            }
+        }
+    }
+}
+
+/// Hand the pending prefill to the page once it has loaded.
+pub fn on_page_load(webview: &tauri::Webview, payload: &tauri::webview::PageLoadPayload<'_>) {
+    if webview.label() != QUICK_ASK_LABEL
+        || payload.event() != tauri::webview::PageLoadEvent::Finished
+    {
+        return;
+    }
+    let app = webview.app_handle();
+    let prefill = app
+        .state::<QuickAskState>()
+        .0
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.take());
+    if let Err(e) = webview.eval(&open_script(app, prefill.as_deref())) {
+        log::warn!("Failed to initialize quick-ask window: {}", e);
+    }
+}
+
+/// Dismiss the popup when it loses focus, like a spotlight-style launcher.
+pub fn on_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    if window.label() == QUICK_ASK_LABEL {
+        if let tauri::WindowEvent::Focused(false) = event {
+            let _ = window.hide();
+        }
+    }
+}
+
+/// Open the quick-ask window, optionally prefilled.
+#[tauri::command]
+pub async fn show_quick_ask(app: tauri::AppHandle, prefill: Option<String>) -> Result<(), String> {
+    show(&app, prefill)
+}
+
+/// Hide the quick-ask window.
+#[tauri::command]
+pub fn hide_quick_ask(app: tauri::AppHandle) {
+    hide(&app);
+}
@@ -0,0 +1,180 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const SESSION_FILE: &str = "session.json";
+
+/// How often to check whether gptme-server is up before restoring.
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Upper bound on server requests made while saving, so quitting stays snappy.
+const SAVE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Open conversations at shutdown, restored on the next launch.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Session {
+    /// Conversation shown in each main-window tab, in tab order. The first
+    /// entry is the main tab; `None` is the conversation list.
+    pub tabs: Vec<Option<String>>,
+    /// Index of the active tab.
+    pub active_tab: usize,
+    /// Conversations open in additional windows, in creation order.
+    pub windows: Vec<Option<String>>,
+    /// Workspace of the conversation the user was looking at. The webui
+    /// reselects it when it receives the `session-restored` event.
+    pub workspace: Option<String>,
+}
+
+/// Managed flag so the session is saved once per shutdown, whichever exit
+/// path gets there first.
+#[derive(Default)]
+pub struct SessionState {
+    saved: AtomicBool,
+}
+
+/// Look up a conversation's workspace on the local server.
+fn workspace(client: &reqwest::blocking::Client, conversation_id: &str) -> Option<String> {
+    let url = crate::events::server_url(&format!("/api/v2/conversations/{}", conversation_id));
+    let response: serde_json::Value = client.get(url).send().ok()?.json().ok()?;
+    response
+        .get("workspace")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+/// Record the open tabs and windows. Call before the server is stopped.
+///
+/// Does nothing once the main window is gone, so a late exit event can't
+/// overwrite the session with an empty one.
+pub fn save(app: &tauri::AppHandle) {
+    if app.get_window("main").is_none() {
+        return;
+    }
+    let Some(state) = app.try_state::<SessionState>() else {
+        return;
+    };
+    if state.saved.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let tabs = match crate::tabs::snapshot(app) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            log::warn!("Failed to save session: {}", e);
+            return;
+        }
+    };
+    let windows = crate::windows::list(app)
+        .into_iter()
+        .filter(|window| window.label != "main")
+        .map(|window| window.conversation_id)
+        .collect();
+
+    let workspace = crate::events::active_conversation(app).and_then(|id| {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(SAVE_TIMEOUT)
+            .build()
+            .ok()?;
+        workspace(&client, &id)
+    });
+
+    let session = Session {
+        active_tab: tabs
+            .tabs
+            .iter()
+            .position(|tab| tab.id == tabs.active)
+            .unwrap_or_default(),
+        tabs: tabs
+            .tabs
+            .into_iter()
+            .map(|tab| tab.conversation_id)
+            .collect(),
+        windows,
+        workspace,
+    };
+    match crate::config::save(app, SESSION_FILE, &session) {
+        Ok(()) => log::info!("Saved session: {:?}", session),
+        Err(e) => log::warn!("Failed to save session: {}", e),
+    }
+}
+
+/// Drop conversations that no longer exist on the server.
+fn existing(client: &reqwest::blocking::Client, conversation_id: Option<String>) -> Option<String> {
+    let id = conversation_id?;
+    let url = crate::events::server_url(&format!("/api/v2/conversations/{}", id));
+    match client.get(url).send() {
+        Ok(response) if response.status().is_success() => Some(id),
+        _ => {
+            log::info!("Not restoring missing conversation {}", id);
+            None
+        }
+    }
+}
+
+/// Show a conversation in the main window's own webview.
+fn navigate_main(app: &tauri::AppHandle, conversation_id: &str) -> Result<(), String> {
+    let webview = app
+        .get_webview(crate::tabs::MAIN_TAB)
+        .ok_or_else(|| "Main window not found".to_string())?;
+    let url = webview
+        .url()
+        .map_err(|e| e.to_string())?
+        .join(&format!("/chat/{}", conversation_id))
+        .map_err(|e| e.to_string())?;
+    webview.navigate(url).map_err(|e| e.to_string())
+}
+
+fn restore(app: &tauri::AppHandle, session: Session) -> Result<(), String> {
+    let client = reqwest::blocking::Client::new();
+    let mut tabs = session.tabs.into_iter();
+
+    if let Some(id) = tabs.next().and_then(|id| existing(&client, id)) {
+        navigate_main(app, &id)?;
+    }
+    for conversation_id in tabs {
+        crate::tabs::create(app, existing(&client, conversation_id))?;
+    }
+    if let Some(tab) = crate::tabs::snapshot(app)?.tabs.get(session.active_tab) {
+        crate::tabs::activate(app, &tab.id)?;
+    }
+
+    for conversation_id in session.windows {
+        crate::windows::open(app, existing(&client, conversation_id))?;
+    }
+    Ok(())
+}
+
+/// Restore the previous session once gptme-server is ready.
+pub fn init(app: &tauri::AppHandle) {
+    app.manage(SessionState::default());
+
+    let session: Session = crate::config::load(app, SESSION_FILE);
+    if session.tabs.iter().all(Option::is_none)
+        && session.windows.is_empty()
+        && session.workspace.is_none()
+    {
+        return;
+    }
+
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        // The server may start late (or lazily at login), so wait for it
+        let client = reqwest::blocking::Client::new();
+        while client
+            .get(crate::events::server_url("/api/v2"))
+            .send()
+            .is_err()
+        {
+            std::thread::sleep(READY_POLL_INTERVAL);
+        }
+
+        log::info!("Restoring session: {:?}", session);
+        if let Err(e) = restore(&handle, session.clone()) {
+            log::error!("Failed to restore session: {}", e);
+        }
+        if let Err(e) = handle.emit("session-restored", &session) {
+            log::warn!("Failed to emit session-restored event: {}", e);
+        }
+    });
+}
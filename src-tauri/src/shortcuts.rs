@@ -0,0 +1,380 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+const SHORTCUTS_FILE: &str = "shortcuts.json";
+
+/// An app action that can be bound to a keyboard shortcut.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    QuickAsk,
+    NewConversation,
+    ToggleWindow,
+    RestartServer,
+    AbortAll,
+}
+
+impl Action {
+    pub const ALL: [Action; 5] = [
+        Action::QuickAsk,
+        Action::NewConversation,
+        Action::ToggleWindow,
+        Action::RestartServer,
+        Action::AbortAll,
+    ];
+
+    /// Global actions work system-wide; the rest are menu accelerators that
+    /// only fire while the app is focused.
+    pub fn is_global(self) -> bool {
+        matches!(
+            self,
+            Action::QuickAsk | Action::ToggleWindow | Action::AbortAll
+        )
+    }
+
+    pub fn default_accelerator(self) -> &'static str {
+        match self {
+            Action::QuickAsk => "CommandOrControl+Shift+Space",
+            Action::NewConversation => "CommandOrControl+N",
+            Action::ToggleWindow => "CommandOrControl+Shift+G",
+            Action::RestartServer => "CommandOrControl+Shift+R",
+            Action::AbortAll => "CommandOrControl+Shift+Period",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::QuickAsk => "Quick Ask",
+            Action::NewConversation => "New Conversation",
+            Action::ToggleWindow => "Toggle Window",
+            Action::RestartServer => "Restart Server",
+            Action::AbortAll => "Stop All Agents",
+        }
+    }
+}
+
+/// Accelerator per action; `None` disables the shortcut. Actions missing
+/// from the saved file use their default.
+type Bindings = BTreeMap<Action, Option<String>>;
+
+/// A binding as sent to the frontend.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBinding {
+    pub action: Action,
+    pub label: &'static str,
+    pub accelerator: Option<String>,
+    pub default_accelerator: &'static str,
+    pub global: bool,
+}
+
+/// Managed state for user-configured shortcuts.
+pub struct ShortcutRegistry {
+    bindings: Mutex<Bindings>,
+    /// Global shortcuts currently registered with the OS.
+    registered: Mutex<Vec<Shortcut>>,
+}
+
+fn with_defaults(mut bindings: Bindings) -> Bindings {
+    for action in Action::ALL {
+        bindings
+            .entry(action)
+            .or_insert_with(|| Some(action.default_accelerator().to_string()));
+    }
+    bindings
+}
+
+fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut '{}': {}", accelerator, e))
+}
+
+/// Check every accelerator parses and none collide with each other or with
+/// the app's fixed shortcuts.
+fn validate(bindings: &Bindings) -> Result<(), String> {
+    let fixed = [
+        (crate::presentation::EXIT_SHORTCUT, "Exit Presentation"),
+        (crate::menu::NEXT_ACCELERATOR, "Next Conversation"),
+        (crate::menu::PREVIOUS_ACCELERATOR, "Previous Conversation"),
+    ]
+    .map(|(accelerator, label)| (accelerator.to_string(), label));
+    let window_menu = (0..9)
+        .filter_map(crate::menu::entry_accelerator)
+        .map(|accelerator| (accelerator, "the Window menu"));
+    let mut used: HashMap<Shortcut, &str> = fixed
+        .into_iter()
+        .chain(window_menu)
+        .filter_map(|(accelerator, label)| parse(&accelerator).ok().map(|s| (s, label)))
+        .collect();
+
+    for (action, accelerator) in bindings {
+        let Some(accelerator) = accelerator else {
+            continue;
+        };
+        if let Some(other) = used.insert(parse(accelerator)?, action.label()) {
+            return Err(format!("'{}' is already used by {}", accelerator, other));
+        }
+    }
+    Ok(())
+}
+
+/// The configured accelerator for an action, if enabled.
+pub fn accelerator(app: &tauri::AppHandle, action: Action) -> Option<String> {
+    app.try_state::<ShortcutRegistry>()
+        .and_then(|registry| {
+            registry
+                .bindings
+                .lock()
+                .ok()
+                .and_then(|bindings| bindings.get(&action).cloned())
+        })
+        .unwrap_or_else(|| Some(action.default_accelerator().to_string()))
+}
+
+/// Perform a shortcut's action.
+pub fn run(app: &tauri::AppHandle, action: Action) {
+    log::info!("Running shortcut action: {:?}", action);
+    // A hidden login launch may still be holding the server back; restarting
+    // starts it anyway
+    if action != Action::RestartServer {
+        let handle = app.clone();
+        tauri::async_runtime::spawn_blocking(move || crate::ensure_server_started(&handle));
+    }
+    match action {
+        Action::QuickAsk => crate::quick_ask::toggle(app),
+        Action::NewConversation => crate::spawn_window_task(app, "open new conversation", |app| {
+            crate::tabs::create(app, None)
+        }),
+        Action::ToggleWindow => match app.get_window("main") {
+            Some(window)
+                if window.is_visible().unwrap_or(false) && window.is_focused().unwrap_or(false) =>
+            {
+                let _ = window.hide();
+            }
+            _ => crate::tray::show_main_window(app),
+        },
+        Action::RestartServer => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::restart_server(app).await {
+                    log::error!("Failed to restart gptme-server: {}", e);
+                }
+            });
+        }
+        Action::AbortAll => {
+            let app = app.clone();
+            std::thread::spawn(move || crate::abort::abort(&app));
+        }
+    }
+}
+
+/// Swap the OS-level registrations for the global actions' shortcuts.
+///
+/// Fails if any shortcut could not be registered, e.g. because another app
+/// already owns it. The others stay registered.
+fn register_global(app: &tauri::AppHandle, bindings: &Bindings) -> Result<(), String> {
+    let registry = app.state::<ShortcutRegistry>();
+    let mut registered = registry
+        .registered
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?;
+
+    for shortcut in registered.drain(..) {
+        if let Err(e) = app.global_shortcut().unregister(shortcut) {
+            log::warn!("Failed to unregister shortcut {:?}: {}", shortcut, e);
+        }
+    }
+
+    let mut failed = Vec::new();
+    for (&action, accelerator) in bindings {
+        let Some(accelerator) = accelerator.as_deref().filter(|_| action.is_global()) else {
+            continue;
+        };
+        let result = parse(accelerator).and_then(|shortcut| {
+            app.global_shortcut()
+                .on_shortcut(shortcut, move |app, _shortcut, event| {
+                    if event.state() != ShortcutState::Pressed {
+                        return;
+                    }
+                    // The emergency stop must work even while presenting
+                    if action != Action::AbortAll && crate::presentation::shortcuts_disabled(app) {
+                        log::info!("Ignoring {:?} shortcut during presentation", action);
+                        return;
+                    }
+                    run(app, action);
+                })
+                .map(|()| shortcut)
+                .map_err(|e| format!("'{}' for {}: {}", accelerator, action.label(), e))
+        });
+        match result {
+            Ok(shortcut) => registered.push(shortcut),
+            Err(e) => failed.push(e),
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to register {}", failed.join("; ")))
+    }
+}
+
+/// Rebuild menus and notify the frontend after a change.
+fn notify(app: &tauri::AppHandle) {
+    crate::menu::rebuild(app);
+    if let Err(e) = app.emit("shortcuts-changed", list(app)) {
+        log::warn!("Failed to emit shortcuts-changed event: {}", e);
+    }
+}
+
+fn list(app: &tauri::AppHandle) -> Vec<ShortcutBinding> {
+    Action::ALL
+        .into_iter()
+        .map(|action| ShortcutBinding {
+            action,
+            label: action.label(),
+            accelerator: accelerator(app, action),
+            default_accelerator: action.default_accelerator(),
+            global: action.is_global(),
+        })
+        .collect()
+}
+
+/// Replace the bindings after validating and registering them, then persist.
+///
+/// If registration or saving fails, the previous bindings are restored and
+/// nothing is saved.
+fn update(app: &tauri::AppHandle, bindings: Bindings) -> Result<(), String> {
+    validate(&bindings)?;
+    let registry = app.state::<ShortcutRegistry>();
+    let previous = registry
+        .bindings
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .clone();
+
+    let result = register_global(app, &bindings)
+        .and_then(|()| crate::config::save(app, SHORTCUTS_FILE, &bindings));
+    if let Err(e) = result {
+        if let Err(e) = register_global(app, &previous) {
+            log::warn!("Failed to restore previous shortcuts: {}", e);
+        }
+        return Err(e);
+    }
+
+    *registry
+        .bindings
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))? = bindings;
+    notify(app);
+    Ok(())
+}
+
+/// Load saved shortcuts and register the global ones.
+pub fn init(app: &tauri::AppHandle) {
+    let mut bindings = with_defaults(crate::config::load(app, SHORTCUTS_FILE));
+    if let Err(e) = validate(&bindings) {
+        log::warn!("Saved shortcuts are invalid, using defaults: {}", e);
+        bindings = with_defaults(Bindings::new());
+    }
+    app.manage(ShortcutRegistry {
+        bindings: Mutex::new(bindings.clone()),
+        registered: Mutex::new(Vec::new()),
+    });
+    if let Err(e) = register_global(app, &bindings) {
+        log::error!("{}", e);
+    }
+}
+
+/// List all shortcut bindings.
+#[tauri::command]
+pub fn get_shortcuts(app: tauri::AppHandle) -> Vec<ShortcutBinding> {
+    list(&app)
+}
+
+/// Bind an action to an accelerator (or `null` to disable it).
+///
+/// Fails without changing anything if the accelerator is invalid, already
+/// in use, or can't be registered with the OS.
+#[tauri::command]
+pub fn set_shortcut(
+    app: tauri::AppHandle,
+    action: Action,
+    accelerator: Option<String>,
+) -> Result<(), String> {
+    let mut bindings = app
+        .state::<ShortcutRegistry>()
+        .bindings
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .clone();
+    let accelerator = accelerator
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
+    bindings.insert(action, accelerator);
+    update(&app, bindings)
+}
+
+/// Restore all shortcuts to their defaults.
+#[tauri::command]
+pub fn reset_shortcuts(app: tauri::AppHandle) -> Result<(), String> {
+    update(&app, with_defaults(Bindings::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bind(bindings: &mut Bindings, action: Action, accelerator: &str) {
+        bindings.insert(action, Some(accelerator.to_string()));
+    }
+
+    #[test]
+    fn validate_accepts_defaults() {
+        assert!(validate(&with_defaults(Bindings::new())).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_duplicates() {
+        let mut bindings = with_defaults(Bindings::new());
+        bind(&mut bindings, Action::QuickAsk, "CommandOrControl+Shift+G");
+        assert!(validate(&bindings).is_err());
+    }
+
+    #[test]
+    fn validate_rejects_reserved_shortcuts() {
+        for accelerator in [
+            "CommandOrControl+Shift+F11",
+            "CmdOrCtrl+Shift+]",
+            "CmdOrCtrl+2",
+        ] {
+            let mut bindings = with_defaults(Bindings::new());
+            bind(&mut bindings, Action::QuickAsk, accelerator);
+            assert!(
+                validate(&bindings).is_err(),
+                "{} should be reserved",
+                accelerator
+            );
+        }
+    }
+
+    #[test]
+    fn validate_ignores_disabled_shortcuts() {
+        let mut bindings = with_defaults(Bindings::new());
+        bindings.insert(Action::QuickAsk, None);
+        bindings.insert(Action::ToggleWindow, None);
+        assert!(validate(&bindings).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_accelerators() {
+        let mut bindings = with_defaults(Bindings::new());
+        bind(&mut bindings, Action::QuickAsk, "Ctrl+NotAKey");
+        assert!(validate(&bindings).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+use crate::events::ServerEvent;
+
+/// What the agent in the active conversation is currently doing.
+#[derive(Default)]
+struct Activity {
+    generating: bool,
+    /// Name of the tool being executed (e.g. `shell`), if any.
+    running_tool: Option<String>,
+    /// Tool calls awaiting confirmation, by tool ID.
+    pending_tools: HashMap<String, String>,
+    /// Last label pushed to the tray and window title.
+    label: String,
+}
+
+impl Activity {
+    fn label(&self) -> String {
+        if let Some(tool) = &self.running_tool {
+            format!("Running {} command", tool)
+        } else if self.generating {
+            "Generating…".to_string()
+        } else if !self.pending_tools.is_empty() {
+            format!("Idle — {} queued", self.pending_tools.len())
+        } else {
+            "Idle".to_string()
+        }
+    }
+}
+
+/// Managed state for the live activity status.
+#[derive(Default)]
+pub struct StatusState(Mutex<Activity>);
+
+/// The current activity label, e.g. "Generating…" or "Idle — 3 queued".
+pub fn current(app: &tauri::AppHandle) -> String {
+    app.try_state::<StatusState>()
+        .and_then(|state| state.0.lock().ok().map(|activity| activity.label()))
+        .unwrap_or_else(|| "Idle".to_string())
+}
+
+/// Push a status label to the tray tooltip, main window title and frontend.
+fn publish(app: &tauri::AppHandle, label: &str) {
+    let name = &app.package_info().name;
+    let text = if label == "Idle" {
+        name.clone()
+    } else {
+        format!("{} — {}", name, label)
+    };
+
+    #[cfg(desktop)]
+    crate::tray::set_tooltip(app, &text);
+    if let Some(window) = app.get_window("main") {
+        if let Err(e) = window.set_title(&text) {
+            log::warn!("Failed to update window title: {}", e);
+        }
+    }
+    if let Err(e) = app.emit("status-changed", label) {
+        log::warn!("Failed to emit status-changed event: {}", e);
+    }
+    crate::discord::update(app, label);
+}
+
+/// Track generation and tool activity from the event bridge.
+pub fn on_server_event(app: &tauri::AppHandle, event: &ServerEvent) {
+    let label = {
+        let state = app.state::<StatusState>();
+        let Ok(mut activity) = state.0.lock() else {
+            return;
+        };
+
+        match event.kind() {
+            // A fresh subscription (e.g. the user switched conversations)
+            "connected" => *activity = Activity::default(),
+            "generation_started" => activity.generating = true,
+            "generation_complete" | "interrupted" | "error" => {
+                activity.generating = false;
+                activity.running_tool = None;
+            }
+            "tool_pending" => {
+                if let Some(tool_id) = event.str_field("tool_id") {
+                    let tool = event
+                        .event
+                        .pointer("/tooluse/tool")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("tool");
+                    activity
+                        .pending_tools
+                        .insert(tool_id.to_string(), tool.to_string());
+                }
+            }
+            "tool_executing" => {
+                let tool = event
+                    .str_field("tool_id")
+                    .and_then(|id| activity.pending_tools.remove(id));
+                activity.running_tool = Some(tool.unwrap_or_else(|| "tool".to_string()));
+            }
+            // Tool output arrives as a system message once execution finishes
+            "message_added" => {
+                if event
+                    .event
+                    .pointer("/message/role")
+                    .and_then(|v| v.as_str())
+                    == Some("system")
+                {
+                    activity.running_tool = None;
+                }
+            }
+            _ => return,
+        }
+
+        let label = activity.label();
+        if label == activity.label {
+            return;
+        }
+        activity.label = label.clone();
+        label
+    };
+
+    publish(app, &label);
+}
+
+/// Get the current activity label shown in the tray tooltip and window title.
+#[tauri::command]
+pub fn get_activity_status(app: tauri::AppHandle) -> String {
+    current(&app)
+}
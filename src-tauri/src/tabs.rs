@@ -0,0 +1,248 @@
+use std::sync::Mutex;
+use tauri::webview::WebviewBuilder;
+use tauri::{Emitter, LogicalPosition, Manager, WebviewUrl};
+
+/// The main window's own webview, which always exists as the first tab.
+pub const MAIN_TAB: &str = "main";
+
+/// Prefix for the labels of additional conversation webviews.
+const TAB_PREFIX: &str = "tab-";
+
+/// A conversation webview hosted in the main window.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tab {
+    pub id: String,
+    /// Conversation currently shown in the tab, read from its URL.
+    pub conversation_id: Option<String>,
+}
+
+/// Tab order and selection, as sent to the frontend.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabsSnapshot {
+    pub tabs: Vec<Tab>,
+    pub active: String,
+}
+
+struct TabModel {
+    /// Webview labels in display order.
+    order: Vec<String>,
+    active: String,
+    next_id: u64,
+}
+
+impl Default for TabModel {
+    fn default() -> Self {
+        Self {
+            order: vec![MAIN_TAB.to_string()],
+            active: MAIN_TAB.to_string(),
+            next_id: 1,
+        }
+    }
+}
+
+/// Managed state for the tabs hosted in the main window.
+#[derive(Default)]
+pub struct TabState(Mutex<TabModel>);
+
+/// Label of the currently active tab.
+pub fn active_tab(app: &tauri::AppHandle) -> String {
+    app.try_state::<TabState>()
+        .and_then(|state| state.0.lock().ok().map(|model| model.active.clone()))
+        .unwrap_or_else(|| MAIN_TAB.to_string())
+}
+
+/// Snapshot of all tabs with their current conversations.
+pub fn snapshot(app: &tauri::AppHandle) -> Result<TabsSnapshot, String> {
+    let state = app.state::<TabState>();
+    let model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let tabs = model
+        .order
+        .iter()
+        .map(|id| Tab {
+            id: id.clone(),
+            conversation_id: app
+                .get_webview(id)
+                .and_then(|webview| webview.url().ok())
+                .and_then(|url| crate::events::conversation_from_url(&url)),
+        })
+        .collect();
+    Ok(TabsSnapshot {
+        tabs,
+        active: model.active.clone(),
+    })
+}
+
+fn emit_changed(app: &tauri::AppHandle) {
+    match snapshot(app) {
+        Ok(snapshot) => {
+            if let Err(e) = app.emit("tabs-changed", &snapshot) {
+                log::warn!("Failed to emit tabs-changed event: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Failed to snapshot tabs: {}", e),
+    }
+    #[cfg(desktop)]
+    crate::menu::rebuild(app);
+}
+
+/// Show one tab's webview and hide all others.
+fn show_only(app: &tauri::AppHandle, model: &TabModel) -> Result<(), String> {
+    for id in &model.order {
+        let Some(webview) = app.get_webview(id) else {
+            continue;
+        };
+        if *id == model.active {
+            webview.show().map_err(|e| e.to_string())?;
+            let _ = webview.set_focus();
+        } else {
+            webview.hide().map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Switch the main window to the given tab.
+pub fn activate(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    {
+        let state = app.state::<TabState>();
+        let mut model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if !model.order.iter().any(|tab| tab == id) {
+            return Err(format!("No such tab: {}", id));
+        }
+        model.active = id.to_string();
+        show_only(app, &model)?;
+    }
+    emit_changed(app);
+    Ok(())
+}
+
+/// Open a new tab in the main window, optionally showing a conversation.
+pub fn create(app: &tauri::AppHandle, conversation_id: Option<String>) -> Result<Tab, String> {
+    let window = app
+        .get_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+    let size = window
+        .inner_size()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(window.scale_factor().map_err(|e| e.to_string())?);
+
+    let url = match &conversation_id {
+        Some(id) => WebviewUrl::App(format!("chat/{}", id).into()),
+        None => WebviewUrl::default(),
+    };
+
+    let label = {
+        let state = app.state::<TabState>();
+        let mut model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let label = format!("{}{}", TAB_PREFIX, model.next_id);
+        model.next_id += 1;
+        label
+    };
+
+    // Don't hold the lock while the main thread builds the webview
+    window
+        .add_child(
+            WebviewBuilder::new(&label, url).auto_resize(),
+            LogicalPosition::new(0.0, 0.0),
+            size,
+        )
+        .map_err(|e| format!("Failed to create tab: {}", e))?;
+
+    {
+        let state = app.state::<TabState>();
+        let mut model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        model.order.push(label.clone());
+        model.active = label.clone();
+        show_only(app, &model)?;
+    }
+    log::info!("Opened tab {} ({:?})", label, conversation_id);
+
+    let tab = Tab {
+        id: label,
+        conversation_id,
+    };
+    emit_changed(app);
+    Ok(tab)
+}
+
+/// Close a tab, activating its neighbour if it was active.
+pub fn close(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    if id == MAIN_TAB {
+        return Err("The main tab cannot be closed".to_string());
+    }
+
+    {
+        let state = app.state::<TabState>();
+        let mut model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let index = model
+            .order
+            .iter()
+            .position(|tab| tab == id)
+            .ok_or_else(|| format!("No such tab: {}", id))?;
+
+        if let Some(webview) = app.get_webview(id) {
+            webview
+                .close()
+                .map_err(|e| format!("Failed to close tab: {}", e))?;
+        }
+        model.order.remove(index);
+
+        if model.active == id {
+            // The main tab can never be removed, so there is always a neighbour
+            model.active = model.order[index.min(model.order.len() - 1)].clone();
+            show_only(app, &model)?;
+        }
+        log::info!("Closed tab {}", id);
+    }
+
+    emit_changed(app);
+    Ok(())
+}
+
+/// Create a new tab, optionally opening a conversation in it.
+#[tauri::command]
+pub async fn create_tab(
+    app: tauri::AppHandle,
+    conversation_id: Option<String>,
+) -> Result<Tab, String> {
+    create(&app, conversation_id)
+}
+
+/// Close a tab.
+#[tauri::command]
+pub fn close_tab(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    close(&app, &id)
+}
+
+/// Switch to a tab.
+#[tauri::command]
+pub fn activate_tab(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    activate(&app, &id)
+}
+
+/// Reorder tabs. `order` must contain exactly the IDs of the open tabs.
+#[tauri::command]
+pub fn reorder_tabs(app: tauri::AppHandle, order: Vec<String>) -> Result<(), String> {
+    {
+        let state = app.state::<TabState>();
+        let mut model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut expected = model.order.clone();
+        let mut given = order.clone();
+        expected.sort();
+        given.sort();
+        if expected != given {
+            return Err("Tab order must contain every open tab exactly once".to_string());
+        }
+        model.order = order;
+    }
+    emit_changed(&app);
+    Ok(())
+}
+
+/// List open tabs and the active one.
+#[tauri::command]
+pub fn list_tabs(app: tauri::AppHandle) -> Result<TabsSnapshot, String> {
+    snapshot(&app)
+}
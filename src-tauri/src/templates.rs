@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{Emitter, Manager};
+
+const TEMPLATES_FILE: &str = "templates.json";
+
+/// A reusable prompt. `{{name}}` placeholders in the body are variables
+/// that get filled in when the template is used.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Template {
+    pub id: String,
+    pub name: String,
+    pub body: String,
+    /// Variable names found in the body, in order of first appearance.
+    #[serde(default)]
+    pub variables: Vec<String>,
+}
+
+/// A template as submitted by the frontend; omit `id` to create a new one.
+#[derive(Debug, serde::Deserialize)]
+pub struct TemplateInput {
+    pub id: Option<String>,
+    pub name: String,
+    pub body: String,
+}
+
+/// Managed state holding all saved templates.
+pub struct TemplateStore(Mutex<Vec<Template>>);
+
+/// Find `{{name}}` placeholders in a template body.
+pub fn variables(body: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        if !name.is_empty() && !found.iter().any(|v| v == name) {
+            found.push(name.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    found
+}
+
+/// Substitute variables into a template body. Variables without a value are
+/// left as-is so the user can fill them in by hand.
+pub fn render(body: &str, values: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        match values.get(after[..end].trim()) {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// All saved templates.
+pub fn list(app: &tauri::AppHandle) -> Vec<Template> {
+    app.try_state::<TemplateStore>()
+        .and_then(|store| store.0.lock().ok().map(|templates| templates.clone()))
+        .unwrap_or_default()
+}
+
+/// Look up a template by ID.
+pub fn get(app: &tauri::AppHandle, id: &str) -> Option<Template> {
+    list(app).into_iter().find(|t| t.id == id)
+}
+
+/// Persist templates and notify the UI, tray and quick-ask window.
+fn save_all(app: &tauri::AppHandle, templates: &[Template]) -> Result<(), String> {
+    crate::config::save(app, TEMPLATES_FILE, &templates)?;
+    if let Err(e) = app.emit("templates-changed", templates) {
+        log::warn!("Failed to emit templates-changed event: {}", e);
+    }
+    #[cfg(desktop)]
+    crate::tray::rebuild_menu(app);
+    Ok(())
+}
+
+/// Load saved templates from the app config dir.
+pub fn init(app: &tauri::AppHandle) {
+    let mut templates: Vec<Template> = crate::config::load(app, TEMPLATES_FILE);
+    for template in &mut templates {
+        template.variables = variables(&template.body);
+    }
+    log::info!("Loaded {} prompt templates", templates.len());
+    app.manage(TemplateStore(Mutex::new(templates)));
+}
+
+/// List saved prompt templates.
+#[tauri::command]
+pub fn list_templates(app: tauri::AppHandle) -> Vec<Template> {
+    list(&app)
+}
+
+/// Create or update a prompt template.
+#[tauri::command]
+pub fn save_template(app: tauri::AppHandle, template: TemplateInput) -> Result<Template, String> {
+    if template.name.trim().is_empty() {
+        return Err("Template name must not be empty".to_string());
+    }
+
+    let saved = Template {
+        id: template.id.unwrap_or_else(|| {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default();
+            format!("{:x}", nanos)
+        }),
+        name: template.name.trim().to_string(),
+        variables: variables(&template.body),
+        body: template.body,
+    };
+
+    let templates = {
+        let store = app.state::<TemplateStore>();
+        let mut templates = store.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        match templates.iter_mut().find(|t| t.id == saved.id) {
+            Some(existing) => *existing = saved.clone(),
+            None => templates.push(saved.clone()),
+        }
+        templates.clone()
+    };
+    save_all(&app, &templates)?;
+    Ok(saved)
+}
+
+/// Delete a prompt template.
+#[tauri::command]
+pub fn delete_template(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let templates = {
+        let store = app.state::<TemplateStore>();
+        let mut templates = store.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let before = templates.len();
+        templates.retain(|t| t.id != id);
+        if templates.len() == before {
+            return Err(format!("No such template: {}", id));
+        }
+        templates.clone()
+    };
+    save_all(&app, &templates)
+}
+
+/// Render a template with the given variable values.
+#[tauri::command]
+pub fn render_template(
+    app: tauri::AppHandle,
+    id: String,
+    values: Option<HashMap<String, String>>,
+) -> Result<String, String> {
+    let template = get(&app, &id).ok_or_else(|| format!("No such template: {}", id))?;
+    Ok(render(&template.body, &values.unwrap_or_default()))
+}
+
+/// Open the quick-ask window prefilled with a rendered template.
+#[tauri::command]
+pub async fn use_template(
+    app: tauri::AppHandle,
+    id: String,
+    values: Option<HashMap<String, String>>,
+) -> Result<(), String> {
+    let prompt = render_template(app.clone(), id, values)?;
+    crate::quick_ask::show(&app, Some(prompt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variables_are_trimmed_and_deduplicated() {
+        assert_eq!(
+            variables("Review {{ file }} for {{lang}}, then {{file}} again"),
+            vec!["file".to_string(), "lang".to_string()]
+        );
+    }
+
+    #[test]
+    fn variables_ignore_unclosed_and_empty_placeholders() {
+        assert_eq!(variables("{{}} and {{ }} and {{open"), Vec::<String>::new());
+        assert_eq!(variables("{{a}} then {{b"), vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn render_substitutes_trimmed_names() {
+        let values = HashMap::from([("name".to_string(), "gptme".to_string())]);
+        assert_eq!(render("Hi {{ name }}!", &values), "Hi gptme!");
+    }
+
+    #[test]
+    fn render_keeps_missing_values() {
+        let values = HashMap::from([("a".to_string(), "1".to_string())]);
+        assert_eq!(render("{{a}} {{ b }} {{a}}", &values), "1 {{ b }} 1");
+    }
+
+    #[test]
+    fn render_keeps_unclosed_placeholder() {
+        let values = HashMap::from([("a".to_string(), "1".to_string())]);
+        assert_eq!(render("{{a}} and {{a", &values), "1 and {{a");
+        assert_eq!(render("no placeholders", &values), "no placeholders");
+    }
+}
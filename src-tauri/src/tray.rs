@@ -0,0 +1,136 @@
+use tauri::menu::{
+    CheckMenuItemBuilder, Menu, MenuBuilder, MenuEvent, MenuItemBuilder, SubmenuBuilder,
+};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::Manager;
+
+/// ID of the app's single tray icon.
+pub const TRAY_ID: &str = "main";
+
+const SHOW_ID: &str = "tray-show";
+const QUICK_ASK_ID: &str = "tray-quick-ask";
+const ABORT_ID: &str = "tray-abort";
+const AUTOSTART_ID: &str = "tray-autostart";
+const QUIT_ID: &str = "tray-quit";
+/// Menu item IDs for templates are `template:<template id>`.
+const TEMPLATE_PREFIX: &str = "template:";
+
+fn build_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let templates = crate::templates::list(app);
+    let mut templates_menu = SubmenuBuilder::new(app, "Templates");
+    if templates.is_empty() {
+        templates_menu = templates_menu.item(
+            &MenuItemBuilder::new("No templates")
+                .enabled(false)
+                .build(app)?,
+        );
+    }
+    for template in templates {
+        templates_menu =
+            templates_menu.text(format!("{}{}", TEMPLATE_PREFIX, template.id), template.name);
+    }
+
+    MenuBuilder::new(app)
+        .text(SHOW_ID, "Show gptme")
+        .text(QUICK_ASK_ID, "Quick Ask…")
+        .item(&templates_menu.build()?)
+        .separator()
+        .text(ABORT_ID, "Stop All Agents")
+        .separator()
+        .item(
+            &CheckMenuItemBuilder::with_id(AUTOSTART_ID, "Launch at Login")
+                .checked(crate::autostart::is_enabled(app))
+                .build(app)?,
+        )
+        .separator()
+        .text(QUIT_ID, "Quit")
+        .build()
+}
+
+/// Rebuild the tray menu, e.g. after templates changed.
+pub fn rebuild_menu(app: &tauri::AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    if let Err(e) = build_menu(app).and_then(|menu| tray.set_menu(Some(menu))) {
+        log::warn!("Failed to rebuild tray menu: {}", e);
+    }
+}
+
+/// Bring the main window to the front.
+pub fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn on_menu_event(app: &tauri::AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        SHOW_ID => show_main_window(app),
+        QUICK_ASK_ID => crate::spawn_window_task(app, "show quick-ask window", |app| {
+            crate::quick_ask::show(app, None)
+        }),
+        ABORT_ID => {
+            let app = app.clone();
+            std::thread::spawn(move || crate::abort::abort(&app));
+        }
+        AUTOSTART_ID => {
+            let enabled = !crate::autostart::is_enabled(app);
+            if let Err(e) = crate::autostart::set_enabled(app, enabled) {
+                log::error!("{}", e);
+                // Undo the check mark the click already toggled
+                rebuild_menu(app);
+            }
+        }
+        QUIT_ID => {
+            log::info!("Quit requested from tray");
+            app.exit(0);
+        }
+        id => {
+            if let Some(template) = id
+                .strip_prefix(TEMPLATE_PREFIX)
+                .and_then(|id| crate::templates::get(app, id))
+            {
+                // Placeholders are left in so the user can fill them in the popup
+                crate::spawn_window_task(app, "show quick-ask window", |app| {
+                    crate::quick_ask::show(app, Some(template.body))
+                });
+            }
+        }
+    }
+}
+
+/// Update the tray icon's tooltip.
+pub fn set_tooltip(app: &tauri::AppHandle, tooltip: &str) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+            log::warn!("Failed to update tray tooltip: {}", e);
+        }
+    }
+}
+
+/// Create the tray icon. Left click shows the main window, right click opens the menu.
+pub fn init(app: &tauri::AppHandle) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(&app.package_info().name)
+        .menu(&build_menu(app)?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Prefix for the labels of additional conversation windows.
+const WINDOW_PREFIX: &str = "conversation-";
+
+/// Groups our windows into native tabs on macOS.
+#[cfg(target_os = "macos")]
+const TABBING_IDENTIFIER: &str = "gptme";
+
+/// An open conversation window, as sent to the frontend.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationWindow {
+    pub label: String,
+    /// Conversation currently shown in the window, read from its URL.
+    pub conversation_id: Option<String>,
+}
+
+struct WindowModel {
+    /// Window labels in creation order, starting with the main window.
+    order: Vec<String>,
+    /// The conversation window that most recently had focus.
+    last_focused: String,
+    next_id: u64,
+}
+
+impl Default for WindowModel {
+    fn default() -> Self {
+        Self {
+            order: vec!["main".to_string()],
+            last_focused: "main".to_string(),
+            next_id: 1,
+        }
+    }
+}
+
+/// Managed registry of conversation windows (the main window plus any
+/// opened with `open_conversation_window`).
+#[derive(Default)]
+pub struct WindowRegistry(Mutex<WindowModel>);
+
+/// Labels of all registered windows, in creation order.
+pub fn labels(app: &tauri::AppHandle) -> Vec<String> {
+    app.try_state::<WindowRegistry>()
+        .and_then(|state| state.0.lock().ok().map(|model| model.order.clone()))
+        .unwrap_or_else(|| vec!["main".to_string()])
+}
+
+/// The conversation window that most recently had focus.
+pub fn last_focused(app: &tauri::AppHandle) -> String {
+    app.try_state::<WindowRegistry>()
+        .and_then(|state| state.0.lock().ok().map(|model| model.last_focused.clone()))
+        .unwrap_or_else(|| "main".to_string())
+}
+
+/// All registered windows with their current conversations.
+pub fn list(app: &tauri::AppHandle) -> Vec<ConversationWindow> {
+    labels(app)
+        .into_iter()
+        .map(|label| ConversationWindow {
+            conversation_id: app
+                .get_webview(&label)
+                .and_then(|webview| webview.url().ok())
+                .and_then(|url| crate::events::conversation_from_url(&url)),
+            label,
+        })
+        .collect()
+}
+
+/// Notify the frontend and the Window menu that the registry changed.
+pub fn emit_changed(app: &tauri::AppHandle) {
+    if let Err(e) = app.emit("windows-changed", list(app)) {
+        log::warn!("Failed to emit windows-changed event: {}", e);
+    }
+    #[cfg(desktop)]
+    crate::menu::rebuild(app);
+}
+
+/// Open a conversation in a new window.
+pub fn open(app: &tauri::AppHandle, conversation_id: Option<String>) -> Result<String, String> {
+    let label = {
+        let state = app.state::<WindowRegistry>();
+        let mut model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let label = format!("{}{}", WINDOW_PREFIX, model.next_id);
+        model.next_id += 1;
+        label
+    };
+
+    let url = match &conversation_id {
+        Some(id) => WebviewUrl::App(format!("chat/{}", id).into()),
+        None => WebviewUrl::default(),
+    };
+
+    let builder = WebviewWindowBuilder::new(app, &label, url)
+        .title("gptme")
+        .inner_size(800.0, 600.0);
+    #[cfg(target_os = "macos")]
+    let builder = builder.tabbing_identifier(TABBING_IDENTIFIER);
+    builder
+        .build()
+        .map_err(|e| format!("Failed to open conversation window: {}", e))?;
+    log::info!("Opened window {} ({:?})", label, conversation_id);
+
+    {
+        let state = app.state::<WindowRegistry>();
+        let mut model = state.0.lock().map_err(|e| format!("Lock error: {}", e))?;
+        model.order.push(label.clone());
+        model.last_focused = label.clone();
+    }
+    emit_changed(app);
+    Ok(label)
+}
+
+/// Keep the registry in sync with window focus and lifetime.
+pub fn on_window_event(window: &tauri::Window, event: &tauri::WindowEvent) {
+    let app = window.app_handle();
+    let Some(state) = app.try_state::<WindowRegistry>() else {
+        return;
+    };
+    let changed = {
+        let Ok(mut model) = state.0.lock() else {
+            return;
+        };
+        let label = window.label().to_string();
+        if !model.order.contains(&label) {
+            return;
+        }
+        match event {
+            tauri::WindowEvent::Focused(true) if model.last_focused != label => {
+                model.last_focused = label;
+                true
+            }
+            tauri::WindowEvent::Destroyed if label != "main" => {
+                model.order.retain(|l| *l != label);
+                if model.last_focused == label {
+                    model.last_focused = "main".to_string();
+                }
+                true
+            }
+            _ => false,
+        }
+    };
+    if changed {
+        emit_changed(app);
+    }
+}
+
+/// Open a conversation in a new window (grouped as a native tab on macOS).
+#[tauri::command]
+pub async fn open_conversation_window(
+    app: tauri::AppHandle,
+    conversation_id: Option<String>,
+) -> Result<String, String> {
+    open(&app, conversation_id)
+}
+
+/// List open conversation windows.
+#[tauri::command]
+pub fn list_conversation_windows(app: tauri::AppHandle) -> Vec<ConversationWindow> {
+    list(&app)
+}